@@ -0,0 +1,58 @@
+//! Generates the registry of the `solutions` binary (see `src/solutions.rs`), which includes every
+//! solution in `src/bin` as a module so that all days can run in a single process.
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/bin");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let mut solutions: Vec<(String, PathBuf)> = fs::read_dir(manifest_dir.join("src/bin"))
+        .map(|entries| entries.flatten().map(|e| e.path()).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|path| Some((module_name(&path)?, path)))
+        .filter(|(_, path)| is_registrable(path))
+        .collect();
+
+    solutions.sort();
+
+    let mut registry = String::new();
+
+    for (module, path) in &solutions {
+        registry += &format!(
+            "#[path = {:?}]\nmod {module};\n",
+            path.display().to_string()
+        );
+    }
+
+    registry += "\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n";
+    for (module, _) in &solutions {
+        registry += &format!("    {module}::SOLUTION,\n");
+    }
+    registry += "];\n";
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solutions.rs"), registry).unwrap();
+}
+
+/// Returns the module name for a solution file like `01.rs` or `2024-01.rs`.
+fn module_name(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let is_solution = path.extension()? == "rs"
+        && matches!(stem.len(), 2 | 7)
+        && stem
+            .chars()
+            .enumerate()
+            .all(|(i, c)| c.is_ascii_digit() || (i == 4 && c == '-'));
+
+    is_solution.then(|| format!("day_{}", stem.replace('-', "_")))
+}
+
+/// Solutions with crate-level attributes (like `#![feature(...)]`) cannot be compiled as a module,
+/// they keep running as a binary of their own.
+fn is_registrable(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|source| {
+        source.contains("solution!(") && !source.lines().any(|l| l.trim_start().starts_with("#!["))
+    })
+}
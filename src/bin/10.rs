@@ -3,7 +3,7 @@ use std::fmt::Display;
 use advent_of_code::algebra_helpers::{Point2, Point2Direction, PointGrid};
 use itertools::Itertools;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, parse = parse);
 
 #[derive(Debug, Default)]
 struct TopoMap(PointGrid<isize, 2, u8>);
@@ -84,13 +84,15 @@ impl TopoMap {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let topomap = TopoMap::from(input);
+fn parse(input: &str) -> TopoMap {
+    TopoMap::from(input)
+}
+
+fn part_one(topomap: &TopoMap) -> Option<u32> {
     Some(topomap.score())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let topomap = TopoMap::from(input);
+fn part_two(topomap: &TopoMap) -> Option<u32> {
     Some(topomap.rating())
 }
 
@@ -100,13 +102,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(36));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(81));
     }
 }
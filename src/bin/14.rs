@@ -73,6 +73,23 @@ pub fn _part_one(input: &str, bounds: Point2<isize>) -> Option<u32> {
     Some(safety_factor as u32)
 }
 
+/// Counts the robots per quadrant in a single pass, instead of scanning the robots once per quadrant.
+fn _part_one_single_pass(input: &str, bounds: Point2<isize>) -> Option<u32> {
+    let (mid_x, mid_y) = (bounds.0[0] / 2, bounds.0[1] / 2);
+    let mut quadrants = [0u32; 4];
+
+    for robot in input.trim().lines().map(Robot::from) {
+        let position = robot.position_after_steps(100, bounds);
+        let (x, y) = (position.0[0], position.0[1]);
+        if x == mid_x || y == mid_y {
+            continue;
+        }
+        quadrants[usize::from(x > mid_x) + 2 * usize::from(y > mid_y)] += 1;
+    }
+
+    Some(quadrants.iter().product())
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     _part_one(input, Point2::new(101, 103))
 }
@@ -92,6 +109,13 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
+    advent_of_code::compare_variants! {
+        1 => [
+            "part_one" => part_one,
+            "single_pass" => |input| _part_one_single_pass(input, Point2::new(101, 103)),
+        ],
+    }
+
     #[test]
     fn test_part_one() {
         let result = _part_one(
@@ -101,6 +125,15 @@ mod tests {
         assert_eq!(result, Some(12));
     }
 
+    #[test]
+    fn test_part_one_single_pass() {
+        let result = _part_one_single_pass(
+            &advent_of_code::template::read_file("examples", DAY),
+            Point2::new(11, 7),
+        );
+        assert_eq!(result, Some(12));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
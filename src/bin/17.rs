@@ -266,19 +266,5 @@ pub fn part_two(input: &str) -> Option<usize> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(247839002892474));
-    }
+    advent_of_code::example_tests!();
 }
@@ -1,4 +1,7 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::PuzzleId;
+use advent_of_code::template::commands::{
+    all, compare, download, inputs, read, scaffold, solve, time, verify, watch,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -7,72 +10,214 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::time::CompareOptions;
+    use advent_of_code::template::{
+        Columns, Day, Execution, HeapProfiling, InputSource, OutputFormat, PuzzleId, Year,
+    };
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+            refresh: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
-            dhat: bool,
+            heap: HeapProfiling,
             submit: Option<u8>,
+            format: OutputFormat,
+            input: InputSource,
+        },
+        Watch {
+            puzzle: PuzzleId,
+            release: bool,
         },
         All {
             release: bool,
+            year: Option<Year>,
+            format: OutputFormat,
+            execution: Execution,
+            input: InputSource,
+            heap: HeapProfiling,
+        },
+        Verify {
+            release: bool,
+            year: Option<Year>,
+        },
+        Inputs {
+            puzzle: PuzzleId,
+            release: bool,
+        },
+        Compare {
+            puzzle: PuzzleId,
+            bench_time: Option<Duration>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            name: Option<String>,
+            compare: Option<CompareOptions>,
+            year: Option<Year>,
+            bench_time: Option<Duration>,
+            format: OutputFormat,
+            execution: Execution,
+            columns: Columns,
+            heap: HeapProfiling,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Parses a puzzle given as `DD` or `YYYY-DD`, letting `--year` override the year.
+    fn puzzle_from_args(
+        args: &mut pico_args::Arguments,
+        year: Option<Year>,
+    ) -> Result<PuzzleId, pico_args::Error> {
+        let puzzle: PuzzleId = args.free_from_str()?;
+        Ok(PuzzleId::new(year.or(puzzle.year), puzzle.day))
+    }
+
+    /// Parses how `all` and `time` run the days: `--isolated` or `--parallel`, in-process by default.
+    fn execution_from_args(args: &mut pico_args::Arguments) -> Execution {
+        if args.contains("--isolated") {
+            Execution::Isolated
+        } else if args.contains("--parallel") {
+            Execution::Parallel
+        } else {
+            Execution::InProcess
+        }
+    }
+
+    /// Parses how the heap usage of runs is measured: `--count-allocs` or `--dhat`, not at all by default.
+    fn heap_profiling_from_args(args: &mut pico_args::Arguments) -> HeapProfiling {
+        if args.contains("--dhat") {
+            HeapProfiling::Dhat
+        } else if args.contains("--count-allocs") {
+            HeapProfiling::Count
+        } else {
+            HeapProfiling::Off
+        }
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+        let format: OutputFormat = args.opt_value_from_str("--format")?.unwrap_or_default();
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                year,
+                format,
+                execution: execution_from_args(&mut args),
+                // a single input file or stdin cannot be shared by all days, but their examples can.
+                input: InputSource::from_options(None, args.opt_value_from_str("--example")?)?,
+                heap: heap_profiling_from_args(&mut args),
             },
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
+                year,
+            },
+            Some("inputs") => AppArguments::Inputs {
+                puzzle: puzzle_from_args(&mut args, year)?,
+                release: args.contains("--release"),
+            },
+            Some("compare") => {
+                let bench_time: Option<u64> = args.opt_value_from_str("--bench-time")?;
+
+                AppArguments::Compare {
+                    puzzle: puzzle_from_args(&mut args, year)?,
+                    bench_time: bench_time.map(Duration::from_millis),
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let name = args.opt_value_from_str("--name")?;
+                let columns = args.opt_value_from_str("--columns")?.unwrap_or_default();
+                let bench_time: Option<u64> = args.opt_value_from_str("--bench-time")?;
+                let execution = execution_from_args(&mut args);
+                let heap = heap_profiling_from_args(&mut args);
+
+                // days benched at the same time compete for cores and memory bandwidth, and their timings end
+                // up in the stored benchmarks.
+                if execution == Execution::Parallel {
+                    return Err("days cannot be timed with `--parallel`.".into());
+                }
+
+                let compare = if args.contains("--compare") {
+                    Some(CompareOptions {
+                        baseline: args.opt_value_from_str("--baseline")?,
+                        threshold: args.opt_value_from_str("--threshold")?,
+                    })
+                } else {
+                    None
+                };
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    name,
+                    compare,
+                    year,
+                    bench_time: bench_time.map(Duration::from_millis),
+                    format,
+                    execution,
+                    columns,
+                    heap,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle_from_args(&mut args, year)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle_from_args(&mut args, year)?,
+                refresh: args.contains("--refresh"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: puzzle_from_args(&mut args, year)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("solve") => {
+                let submit = args.opt_value_from_str("--submit")?;
+                let input = InputSource::from_options(
+                    args.opt_value_from_str("--input")?,
+                    args.opt_value_from_str("--example")?,
+                )?;
+
+                if submit.is_some() && !input.is_puzzle_input() {
+                    return Err("only answers for the puzzle input can be submitted.".into());
+                }
+
+                AppArguments::Solve {
+                    puzzle: puzzle_from_args(&mut args, year)?,
+                    release: args.contains("--release"),
+                    submit,
+                    heap: heap_profiling_from_args(&mut args),
+                    format,
+                    input,
+                }
+            }
+            Some("watch") => AppArguments::Watch {
+                puzzle: puzzle_from_args(&mut args, year)?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -95,6 +240,22 @@ mod args {
     }
 }
 
+/// Scaffolds a puzzle, downloading it first if asked to, so that the module gets the title of the puzzle.
+/// A failed download does not keep the module from being created, but still fails the command.
+fn scaffold_puzzle(puzzle: PuzzleId, download: bool, overwrite: bool, template: Option<&str>) {
+    let downloaded = if download {
+        download::try_download(puzzle).inspect_err(|e| eprintln!("{e}"))
+    } else {
+        Ok(())
+    };
+
+    scaffold::handle(puzzle, overwrite, template);
+
+    if downloaded.is_err() {
+        std::process::exit(1);
+    }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -102,33 +263,56 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                release,
+                year,
+                format,
+                execution,
+                input,
+                heap,
+            } => all::handle(release, year, format, execution, &input, heap),
+            AppArguments::Verify { release, year } => verify::handle(release, year),
+            AppArguments::Inputs { puzzle, release } => inputs::handle(puzzle, release),
+            AppArguments::Compare { puzzle, bench_time } => compare::handle(puzzle, bench_time),
+            AppArguments::Time {
                 day,
+                all,
+                store,
+                name,
+                compare,
+                year,
+                bench_time,
+                format,
+                execution,
+                columns,
+                heap,
+            } => time::handle(
+                day, all, store, name, compare, year, bench_time, format, execution, &columns, heap,
+            ),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle, refresh } => read::handle(puzzle, refresh),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
+                template,
+            } => scaffold_puzzle(puzzle, download, overwrite, template.as_deref()),
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
-                dhat,
+                heap,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                format,
+                input,
+            } => solve::handle(puzzle, release, heap, submit, format, &input),
+            AppArguments::Watch { puzzle, release } => watch::handle(puzzle, release),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = day.into();
+                        scaffold_puzzle(puzzle, true, false, None);
+                        read::handle(puzzle, false)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,21 @@
+//! Runs every solution in one process. The registry is generated by `build.rs`, see
+//! [`advent_of_code::template::registry`] for the supported arguments.
+
+// the global allocators of the solutions would conflict with each other.
+#[cfg(not(feature = "dhat-heap"))]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn main() {
+    advent_of_code::template::registry::run(registry::SOLUTIONS);
+}
+
+#[cfg(feature = "dhat-heap")]
+fn main() {
+    eprintln!(
+        "The solutions binary does not support heap profiling, run a single day with `--dhat` instead."
+    );
+    std::process::exit(1);
+}
@@ -0,0 +1,330 @@
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::SubmissionOutcome;
+use crate::template::{Day, Year, data_dir};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents a single submitted answer and the verdict of the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+}
+
+/// A ledger of all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// Reasons for refusing a submission without asking the server.
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    AlreadyCorrect(String),
+    AlreadyRejected(SubmissionOutcome),
+    NotBelow(i128),
+    NotAbove(i128),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect(answer) => {
+                write!(f, "this part was already solved with answer `{answer}`.")
+            }
+            Refusal::AlreadyRejected(outcome) => {
+                write!(f, "this answer was already submitted and was {outcome}.")
+            }
+            Refusal::NotBelow(bound) => {
+                write!(f, "`{bound}` was too high, the answer must be lower.")
+            }
+            Refusal::NotAbove(bound) => {
+                write!(f, "`{bound}` was too low, the answer must be higher.")
+            }
+        }
+    }
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of a year.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_dir(year).join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of a year. If not present, returns an empty ledger.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let Ok(s) = fs::read_to_string(data_dir(year).join(ANSWERS_FILE_NAME)) else {
+            return Answers::default();
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Records the verdict for an answer. Verdicts that do not judge the answer itself are ignored.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, outcome: SubmissionOutcome) {
+        if matches!(
+            outcome,
+            SubmissionOutcome::AlreadySolved | SubmissionOutcome::RateLimited { .. }
+        ) {
+            return;
+        }
+
+        self.data.push(Answer {
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+        });
+    }
+
+    /// Returns the answer that was accepted for a part, if any.
+    pub fn correct_answer(&self, day: Day, part: u8) -> Option<&str> {
+        self.for_part(day, part)
+            .find(|a| a.outcome.is_correct())
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Checks whether an answer is worth submitting, given all previous verdicts for that part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        if let Some(correct) = self.correct_answer(day, part) {
+            return Err(Refusal::AlreadyCorrect(correct.to_string()));
+        }
+
+        if let Some(previous) = self.for_part(day, part).find(|a| a.answer == answer) {
+            return Err(Refusal::AlreadyRejected(previous.outcome));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let numeric_with = |outcome: SubmissionOutcome| {
+            self.for_part(day, part)
+                .filter(move |a| a.outcome == outcome)
+                .filter_map(|a| a.answer.parse::<i128>().ok())
+        };
+
+        if let Some(upper) = numeric_with(SubmissionOutcome::TooHigh).min()
+            && value >= upper
+        {
+            return Err(Refusal::NotBelow(upper));
+        }
+
+        if let Some(lower) = numeric_with(SubmissionOutcome::TooLow).max()
+            && value <= lower
+        {
+            return Err(Refusal::NotAbove(lower));
+        }
+
+        Ok(())
+    }
+
+    fn for_part(&self, day: Day, part: u8) -> impl Iterator<Item = &Answer> {
+        self.data
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let verdict = match value.outcome {
+            SubmissionOutcome::Correct => "correct",
+            SubmissionOutcome::TooHigh => "too_high",
+            SubmissionOutcome::TooLow => "too_low",
+            SubmissionOutcome::Wrong
+            | SubmissionOutcome::AlreadySolved
+            | SubmissionOutcome::RateLimited { .. } => "wrong",
+        };
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert("verdict".into(), JsonValue::String(verdict.into()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .map(|part| *part as u8)
+            .ok_or("Expected answer.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.answer to be a string.")?;
+
+        let outcome = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| match v.as_str() {
+                "correct" => Some(SubmissionOutcome::Correct),
+                "too_high" => Some(SubmissionOutcome::TooHigh),
+                "too_low" => Some(SubmissionOutcome::TooLow),
+                "wrong" => Some(SubmissionOutcome::Wrong),
+                _ => None,
+            })
+            .ok_or(
+                "Expected answer.verdict to be one of `correct`, `too_high`, `too_low`, `wrong`.",
+            )?;
+
+        Ok(Answer {
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Refusal};
+    use crate::{day, template::aoc_client::SubmissionOutcome};
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "100", SubmissionOutcome::TooHigh);
+        answers.record(day!(1), 1, "10", SubmissionOutcome::TooLow);
+        answers.record(day!(1), 1, "abc", SubmissionOutcome::Wrong);
+        answers.record(
+            day!(1),
+            1,
+            "50",
+            SubmissionOutcome::RateLimited {
+                wait: Some(std::time::Duration::from_secs(30)),
+            },
+        );
+        answers.record(day!(2), 2, "42", SubmissionOutcome::Correct);
+        answers
+    }
+
+    #[test]
+    fn ignores_verdicts_without_judgement() {
+        assert_eq!(get_mock_answers().data.len(), 4);
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(day!(1), 1, "abc"),
+            Err(Refusal::AlreadyRejected(SubmissionOutcome::Wrong))
+        );
+        assert_eq!(
+            answers.check(day!(2), 2, "43"),
+            Err(Refusal::AlreadyCorrect("42".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(day!(1), 1, "100"),
+            Err(Refusal::AlreadyRejected(SubmissionOutcome::TooHigh))
+        );
+        assert_eq!(
+            answers.check(day!(1), 1, "120"),
+            Err(Refusal::NotBelow(100))
+        );
+        assert_eq!(answers.check(day!(1), 1, "3"), Err(Refusal::NotAbove(10)));
+        assert_eq!(answers.check(day!(1), 1, "50"), Ok(()));
+        assert_eq!(answers.check(day!(1), 2, "120"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.data, answers.data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part": 3, "answer": "1", "verdict": "correct" }] }"#
+                .to_string();
+        Answers::try_from(json).unwrap();
+    }
+}
@@ -0,0 +1,454 @@
+/// Native client for the Advent of Code puzzle server.
+/// Authenticates via a session token read from `AOC_SESSION` or `~/.adventofcode.session`.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::answers::Answers;
+use crate::template::examples::PageExamples;
+use crate::template::puzzle_text::{self, Flavor};
+use crate::template::{Day, PuzzleId, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " by ",
+    env!("CARGO_PKG_AUTHORS")
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotFound,
+    BadStatus(u16, String),
+    Transport(String),
+    UnexpectedResponse(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session token found. Set `AOC_SESSION` or write it to `~/{SESSION_FILE_NAME}`."
+            ),
+            AocClientError::YearNotFound => {
+                write!(f, "no event year found. Set `AOC_YEAR` to a valid year.")
+            }
+            AocClientError::BadStatus(status, url) => {
+                write!(f, "server responded with status {status} for \"{url}\".")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::UnexpectedResponse(text) => {
+                write!(f, "could not understand server response:\n{text}")
+            }
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                AocClientError::BadStatus(status, response.get_url().to_string())
+            }
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// The verdict of the server on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    AlreadySolved,
+    /// The remaining wait is [`None`] if the response does not tell it in a known format.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+}
+
+impl SubmissionOutcome {
+    /// Classifies the (markdown-converted) response of an answer submission.
+    /// Returns [`None`] if the response does not match any known verdict.
+    pub fn from_response(text: &str) -> Option<Self> {
+        if text.contains("That's the right answer") {
+            Some(SubmissionOutcome::Correct)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(SubmissionOutcome::AlreadySolved)
+        } else if text.contains("You gave an answer too recently") {
+            Some(SubmissionOutcome::RateLimited {
+                wait: parse_wait(text),
+            })
+        } else if text.contains("your answer is too high") {
+            Some(SubmissionOutcome::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(SubmissionOutcome::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Some(SubmissionOutcome::Wrong)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_correct(&self) -> bool {
+        matches!(self, SubmissionOutcome::Correct)
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "correct"),
+            SubmissionOutcome::TooHigh => write!(f, "wrong (too high)"),
+            SubmissionOutcome::TooLow => write!(f, "wrong (too low)"),
+            SubmissionOutcome::Wrong => write!(f, "wrong"),
+            SubmissionOutcome::AlreadySolved => write!(f, "already solved"),
+            SubmissionOutcome::RateLimited { .. } => write!(f, "rate limited"),
+        }
+    }
+}
+
+/// Parses the remaining wait time from a sentence like `You have 1m 5s left to wait.`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, amount) = before.rsplit_once("You have ")?;
+
+    amount
+        .split_whitespace()
+        .try_fold(Duration::ZERO, |acc, token| {
+            let (value, unit) = token.split_at(token.len().checked_sub(1)?);
+            let value: u64 = value.parse().ok()?;
+            let seconds = match unit {
+                "h" => value * 3600,
+                "m" => value * 60,
+                "s" => value,
+                _ => return None,
+            };
+            Some(acc + Duration::from_secs(seconds))
+        })
+}
+
+/// A client bound to a server and a session.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent,
+        }
+    }
+
+    /// Creates a client from the environment.
+    /// The base url can be overridden with `AOC_BASE_URL`, e.g. to point it at a mock server.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&base_url(), &session))
+    }
+
+    /// Fetches the personal puzzle input for a day.
+    pub fn get_input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(year, day));
+        let response = self.authorized(self.agent.get(&url)).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the puzzle page for a day as HTML.
+    pub fn get_puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let url = self.day_url(year, day);
+        let response = self.authorized(self.agent.get(&url)).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Posts an answer for one part of a day and returns the HTML response.
+    pub fn post_answer(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.day_url(year, day));
+        let response = self
+            .authorized(self.agent.post(&url))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        Ok(response.into_string()?)
+    }
+
+    fn day_url(&self, year: Year, day: Day) -> String {
+        format!("{}/{year}/day/{}", self.base_url, day.into_inner())
+    }
+
+    fn authorized(&self, request: ureq::Request) -> ureq::Request {
+        request.set("Cookie", &format!("session={}", self.session))
+    }
+}
+
+/// The url of a puzzle page, if the year of the puzzle is known.
+pub fn puzzle_url(puzzle: PuzzleId) -> Option<String> {
+    let year = puzzle.year_or_env()?;
+    Some(format!(
+        "{}/{year}/day/{}",
+        base_url().trim_end_matches('/'),
+        puzzle.day.into_inner()
+    ))
+}
+
+pub fn check() -> Result<(), AocClientError> {
+    AocClient::from_env().map(|_| ())
+}
+
+/// Shows the description of a puzzle, from the page cached in `data/puzzles/DD.html` if present.
+/// The page is fetched again if `refresh` is set, or if it lacks part two although part one has been solved.
+/// If fetching fails, the cached page is shown instead.
+pub fn read(puzzle: PuzzleId, refresh: bool) -> Result<String, AocClientError> {
+    let cache_path = puzzle.data_path("puzzles", ".html");
+    let cached = fs::read_to_string(&cache_path).ok();
+
+    let html = match cached {
+        Some(html) if !refresh && !is_part_two_unlocked(puzzle, &html) => html,
+        cached => match fetch_puzzle(puzzle) {
+            Ok(html) => html,
+            Err(e) => match cached {
+                Some(html) => {
+                    eprintln!("Could not refresh the puzzle ({e}), showing the cached page.");
+                    html
+                }
+                None => return Err(e),
+            },
+        },
+    };
+
+    let text = puzzle_text::render(&html, Flavor::Markdown);
+    write_data_file(&puzzle.data_path("puzzles", ".md"), &text)?;
+
+    if io::stdout().is_terminal() {
+        println!("{}", puzzle_text::render(&html, Flavor::Ansi));
+    } else {
+        println!("{text}");
+    }
+    Ok(text)
+}
+
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let year = get_year(puzzle)?;
+    let input_path = puzzle.data_path("inputs", ".txt");
+    let puzzle_path = puzzle.data_path("puzzles", ".md");
+
+    write_data_file(&input_path, &client.get_input(year, puzzle.day)?)?;
+    let html = client.get_puzzle(year, puzzle.day)?;
+    write_data_file(&puzzle.data_path("puzzles", ".html"), &html)?;
+    write_data_file(&puzzle_path, &puzzle_text::render(&html, Flavor::Markdown))?;
+    let examples = PageExamples::from_html(&html).store(puzzle)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    for path in examples {
+        println!("🎄 Successfully wrote example to \"{}\".", path.display());
+    }
+    Ok(())
+}
+
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<SubmissionOutcome, AocClientError> {
+    let client = AocClient::from_env()?;
+    let year = get_year(puzzle)?;
+    let response = puzzle_text::render(
+        &client.post_answer(year, puzzle.day, part, result)?,
+        Flavor::Markdown,
+    );
+    SubmissionOutcome::from_response(&response).ok_or(AocClientError::UnexpectedResponse(response))
+}
+
+/// Fetches the page of a puzzle and caches it in `data/puzzles/DD.html`.
+fn fetch_puzzle(puzzle: PuzzleId) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let html = client.get_puzzle(get_year(puzzle)?, puzzle.day)?;
+    write_data_file(&puzzle.data_path("puzzles", ".html"), &html)?;
+    Ok(html)
+}
+
+/// Whether part two has been unlocked since a page was cached, according to the recorded answers.
+fn is_part_two_unlocked(puzzle: PuzzleId, html: &str) -> bool {
+    !puzzle_text::has_part_two(html)
+        && Answers::read_from_file(puzzle.year)
+            .correct_answer(puzzle.day, 1)
+            .is_some()
+}
+
+fn get_year(puzzle: PuzzleId) -> Result<Year, AocClientError> {
+    puzzle.year_or_env().ok_or(AocClientError::YearNotFound)
+}
+
+fn write_data_file(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    Ok(fs::write(path, contents)?)
+}
+
+/// The server to talk to, can be overridden with `AOC_BASE_URL`.
+fn base_url() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into())
+}
+
+/// Reads the session token from `AOC_SESSION`, or from the session file if the variable is not set or empty.
+fn get_session() -> Option<String> {
+    let is_set = |session: &String| !session.trim().is_empty();
+
+    if let Some(session) = env::var("AOC_SESSION").ok().filter(is_set) {
+        return Some(session);
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let path = PathBuf::from(home).join(SESSION_FILE_NAME);
+    fs::read_to_string(path).ok().filter(is_set)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, SubmissionOutcome};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    /// Serves a single request with the given body and returns the received request head.
+    fn mock_server(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = String::new();
+
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+                head.push('\n');
+            }
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            head
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, handle) = mock_server("1 2\n3 4\n");
+        let client = AocClient::new(&url, "abc123\n");
+
+        let input = client.get_input(year!(2024), day!(1)).unwrap();
+        let head = handle.join().unwrap();
+
+        assert_eq!(input, "1 2\n3 4\n");
+        assert!(head.starts_with("GET /2024/day/1/input "));
+        assert!(head.contains("session=abc123"));
+    }
+
+    #[test]
+    fn classifies_submission_responses() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                SubmissionOutcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high. Please wait one minute.",
+                SubmissionOutcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmissionOutcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                SubmissionOutcome::Wrong,
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                SubmissionOutcome::AlreadySolved,
+            ),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(SubmissionOutcome::from_response(text), Some(expected));
+        }
+    }
+
+    #[test]
+    fn parses_rate_limit_wait() {
+        let text = "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait.";
+        assert_eq!(
+            SubmissionOutcome::from_response(text),
+            Some(SubmissionOutcome::RateLimited {
+                wait: Some(Duration::from_secs(65))
+            })
+        );
+
+        let text = "You gave an answer too recently; please wait a while.";
+        assert_eq!(
+            SubmissionOutcome::from_response(text),
+            Some(SubmissionOutcome::RateLimited { wait: None })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_responses() {
+        assert_eq!(SubmissionOutcome::from_response("<html></html>"), None);
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Error;
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::report::Phase;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Year, data_dir};
+
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+/// A single stored benchmark run.
+#[derive(Clone, Debug)]
+pub struct BenchRun {
+    /// Seconds since the UNIX epoch at which the run was stored.
+    pub timestamp: u64,
+    /// Short hash of the commit the run was benched at, suffixed with `-dirty` for uncommitted changes.
+    pub commit: Option<String>,
+    /// Optional label that allows using this run as a named baseline.
+    pub name: Option<String>,
+    pub timings: Timings,
+}
+
+/// Every benchmark run stored with `time --store`, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct BenchHistory {
+    pub runs: Vec<BenchRun>,
+}
+
+impl BenchRun {
+    /// Creates a run of the current commit at the current time.
+    pub fn now(timings: Timings, name: Option<String>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            timestamp,
+            commit: current_commit(),
+            name,
+            timings,
+        }
+    }
+
+    /// Describes the run by its name and commit, e.g. `before-refactor (a1b2c3d)`.
+    pub fn label(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("unknown commit");
+        match &self.name {
+            Some(name) => format!("{name} ({commit})"),
+            None => commit.to_string(),
+        }
+    }
+}
+
+impl BenchHistory {
+    /// Dehydrate the history to the JSON file of a year.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(data_dir(year).join(HISTORY_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from the JSON file of a year. If not present, returns an empty history.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        match fs::read_to_string(data_dir(year).join(HISTORY_FILE_NAME)) {
+            Ok(s) => BenchHistory::try_from(s).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                BenchHistory::default()
+            }),
+            Err(_) => BenchHistory::default(),
+        }
+    }
+
+    pub fn push(&mut self, run: BenchRun) {
+        self.runs.push(run);
+    }
+
+    /// Returns the latest run with the given name or a commit starting with it.
+    pub fn find(&self, name: &str) -> Option<&BenchRun> {
+        self.runs.iter().rev().find(|run| {
+            run.name.as_deref() == Some(name)
+                || run.commit.as_deref().is_some_and(|c| c.starts_with(name))
+        })
+    }
+
+    /// Returns the timings to compare a new run against.
+    ///
+    /// Without a name, this is the most recent stored timing of every day.
+    /// With a name, this is the latest run with that name or a commit starting with it.
+    pub fn baseline(&self, name: Option<&str>) -> Option<Timings> {
+        match name {
+            Some(name) => self.find(name).map(|run| run.timings.clone()),
+            None if self.runs.is_empty() => None,
+            None => Some(
+                self.runs
+                    .iter()
+                    .fold(Timings::default(), |acc, run| acc.merge(&run.timings)),
+            ),
+        }
+    }
+}
+
+/// Returns the short hash of the checked out commit, if the current directory is a git repository.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The change in run time of a phase of a day between a baseline and the current run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub phase: Phase,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+}
+
+impl Comparison {
+    /// Relative change of the run time in percent, positive if the phase got slower.
+    pub fn change_percent(&self) -> f64 {
+        if self.baseline_nanos == 0.0 {
+            return 0.0;
+        }
+        (self.current_nanos - self.baseline_nanos) / self.baseline_nanos * 100.0
+    }
+
+    /// Whether the phase got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
+    }
+}
+
+/// Pairs the phases of the current run with their baseline, phase by phase, so that a part that was newly
+/// solved or failed does not show up as a change of the other phases. Phases that were not timed in either
+/// run are skipped.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<Comparison> {
+    current
+        .data
+        .iter()
+        .filter_map(|timing| Some((baseline.data.iter().find(|t| t.day == timing.day)?, timing)))
+        .flat_map(|(before, timing)| {
+            [Phase::Parse, Phase::Part(1), Phase::Part(2)]
+                .into_iter()
+                .filter_map(move |phase| {
+                    Some(Comparison {
+                        day: timing.day,
+                        phase,
+                        baseline_nanos: phase_nanos(before, phase)?,
+                        current_nanos: phase_nanos(timing, phase)?,
+                    })
+                })
+        })
+        .collect()
+}
+
+/// The median run time of a phase, if it was timed.
+fn phase_nanos(timing: &Timing, phase: Phase) -> Option<f64> {
+    let stats = match phase {
+        Phase::Parse => timing.parse_stats,
+        Phase::Part(1) => timing.part_1_stats,
+        Phase::Part(2) => timing.part_2_stats,
+        Phase::Part(_) => None,
+    };
+
+    stats.map(|stats| stats.median.as_nanos() as f64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchHistory> for JsonValue {
+    fn from(value: &BenchHistory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.runs.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for BenchHistory {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(BenchHistory {
+            runs: json_runs
+                .iter()
+                .map(BenchRun::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&BenchRun> for JsonValue {
+    fn from(value: &BenchRun) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let string = |s: &Option<String>| s.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), string(&value.commit));
+        map.insert("name".into(), string(&value.name));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchRun {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected run.timestamp to be a number.")? as u64;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(BenchRun {
+            timestamp,
+            commit: string("commit"),
+            name: string("name"),
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{BenchHistory, BenchRun, compare};
+    use crate::day;
+    use crate::template::Day;
+    use crate::template::report::Phase;
+    use crate::template::stats::BenchStats;
+    use crate::template::timings::{Timing, Timings};
+
+    fn stats(nanos: f64) -> Option<BenchStats> {
+        BenchStats::from_samples(&[Duration::from_nanos(nanos as u64)])
+    }
+
+    fn timings(values: &[(Day, f64)]) -> Timings {
+        Timings {
+            data: values
+                .iter()
+                .map(|(day, total_nanos)| Timing {
+                    part_1: Some("1ms".into()),
+                    part_1_stats: stats(*total_nanos),
+                    ..Timing::for_day(*day, *total_nanos)
+                })
+                .collect(),
+        }
+    }
+
+    fn run(name: Option<&str>, commit: &str, values: &[(Day, f64)]) -> BenchRun {
+        BenchRun {
+            timestamp: 1_733_000_000,
+            commit: Some(commit.into()),
+            name: name.map(String::from),
+            timings: timings(values),
+        }
+    }
+
+    fn get_mock_history() -> BenchHistory {
+        BenchHistory {
+            runs: vec![
+                run(
+                    Some("initial"),
+                    "aaaaaaa",
+                    &[(day!(1), 100.0), (day!(2), 200.0)],
+                ),
+                run(None, "bbbbbbb", &[(day!(2), 150.0)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn uses_latest_timing_of_each_day_as_baseline() {
+        let baseline = get_mock_history().baseline(None).unwrap();
+        assert_eq!(baseline.data.len(), 2);
+        assert_eq!(baseline.data[0].total_nanos, 100.0);
+        assert_eq!(baseline.data[1].total_nanos, 150.0);
+    }
+
+    #[test]
+    fn finds_named_baselines() {
+        let history = get_mock_history();
+        let by_name = history.baseline(Some("initial")).unwrap();
+        assert_eq!(by_name.data[1].total_nanos, 200.0);
+
+        let by_commit = history.baseline(Some("bbb")).unwrap();
+        assert_eq!(by_commit.data.len(), 1);
+
+        assert!(history.baseline(Some("unknown")).is_none());
+        assert!(BenchHistory::default().baseline(None).is_none());
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let baseline = timings(&[(day!(1), 100.0), (day!(2), 200.0)]);
+        let current = timings(&[(day!(1), 125.0), (day!(2), 190.0), (day!(3), 50.0)]);
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].phase, Phase::Part(1));
+        assert_eq!(comparisons[0].change_percent(), 25.0);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(30.0));
+        assert_eq!(comparisons[1].change_percent(), -5.0);
+        assert!(!comparisons[1].is_regression(0.0));
+    }
+
+    #[test]
+    fn compares_phase_by_phase() {
+        let baseline = timings(&[(day!(1), 100.0)]);
+        let mut current = timings(&[(day!(1), 100.0)]);
+        // a newly solved part 2 adds to the total, but is not a change of part 1.
+        current.data[0].part_2 = Some("400ns".into());
+        current.data[0].part_2_stats = stats(400.0);
+        current.data[0].total_nanos = 500.0;
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].phase, Phase::Part(1));
+        assert_eq!(comparisons[0].change_percent(), 0.0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let history = get_mock_history();
+        let json = tinyjson::JsonValue::from(&history).stringify().unwrap();
+        let parsed = BenchHistory::try_from(json).unwrap();
+
+        assert_eq!(parsed.runs.len(), 2);
+        assert_eq!(parsed.runs[0].name.as_deref(), Some("initial"));
+        assert_eq!(parsed.runs[1].name, None);
+        assert_eq!(parsed.runs[1].commit.as_deref(), Some("bbbbbbb"));
+        assert_eq!(parsed.runs[1].timestamp, 1_733_000_000);
+        assert_eq!(parsed.runs[0].timings.data.len(), 2);
+    }
+}
@@ -0,0 +1,159 @@
+/// Renders the stored timings as an SVG bar chart, with one bar per day that is split into its phases.
+/// The chart is written to `data/visualizations/benchmarks.svg` and linked from the benchmark table.
+use std::{fs, io, path::PathBuf, time::Duration};
+
+use crate::template::stats::BenchStats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Year, data_dir};
+
+static CHART_FILE_NAME: &str = "benchmarks.svg";
+
+const ROW_HEIGHT: usize = 28;
+const BAR_HEIGHT: usize = 18;
+const LABEL_WIDTH: usize = 70;
+const CHART_WIDTH: usize = 480;
+const TOTAL_WIDTH: usize = 90;
+const MARGIN: usize = 10;
+const LEGEND_HEIGHT: usize = 30;
+
+/// Phases of a day with their color, in the order they are stacked.
+const PHASES: [(&str, &str); 3] = [
+    ("Parse", "#8da0cb"),
+    ("Part 1", "#66c2a5"),
+    ("Part 2", "#fc8d62"),
+];
+
+/// The path of the chart relative to the repository root, e.g. `data/visualizations/benchmarks.svg`.
+pub fn chart_path(year: Option<Year>) -> PathBuf {
+    data_dir(year).join("visualizations").join(CHART_FILE_NAME)
+}
+
+/// Writes the chart of the timings to [`chart_path`].
+pub fn store_file(timings: &Timings, year: Option<Year>) -> Result<PathBuf, io::Error> {
+    let path = chart_path(year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, render(timings))?;
+    Ok(path)
+}
+
+/// The time spent in each phase of a day in nanoseconds. Days timed without stats count as a single phase.
+fn phase_nanos(timing: &Timing) -> [f64; 3] {
+    let median =
+        |stats: Option<BenchStats>| stats.map_or(0.0, |stats| stats.median.as_nanos() as f64);
+
+    let phases = [
+        median(timing.parse_stats),
+        median(timing.part_1_stats),
+        median(timing.part_2_stats),
+    ];
+
+    if phases.iter().sum::<f64>() > 0.0 {
+        phases
+    } else {
+        [0.0, 0.0, timing.total_nanos]
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn render(timings: &Timings) -> String {
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0.0, f64::max);
+
+    let width = LABEL_WIDTH + CHART_WIDTH + TOTAL_WIDTH + 2 * MARGIN;
+    let height = timings.data.len() * ROW_HEIGHT + LEGEND_HEIGHT + 2 * MARGIN;
+
+    let mut lines = vec![
+        format!(
+            r#"<svg width="{width}" height="{height}" viewBox="0 0 {width} {height}" xmlns="http://www.w3.org/2000/svg" font-family="sans-serif" font-size="12">"#
+        ),
+        format!(r#"<rect width="{width}" height="{height}" fill="white"/>"#),
+    ];
+
+    for (row, timing) in timings.data.iter().enumerate() {
+        let y = MARGIN + row * ROW_HEIGHT;
+        let text_y = y + BAR_HEIGHT - 5;
+        let mut x = (MARGIN + LABEL_WIDTH) as f64;
+
+        lines.push(format!(
+            r#"<text x="{MARGIN}" y="{text_y}">Day {}</text>"#,
+            timing.day.into_inner()
+        ));
+
+        for ((name, color), nanos) in PHASES.iter().zip(phase_nanos(timing)) {
+            if nanos <= 0.0 || max_nanos <= 0.0 {
+                continue;
+            }
+
+            let bar_width = nanos / max_nanos * CHART_WIDTH as f64;
+            lines.push(format!(
+                r#"<rect x="{x:.1}" y="{y}" width="{bar_width:.1}" height="{BAR_HEIGHT}" fill="{color}"><title>{name}: {:.1?}</title></rect>"#,
+                Duration::from_nanos(nanos as u64)
+            ));
+            x += bar_width;
+        }
+
+        lines.push(format!(
+            r#"<text x="{:.1}" y="{text_y}">{:.1?}</text>"#,
+            x + 5.0,
+            Duration::from_nanos(timing.total_nanos as u64)
+        ));
+    }
+
+    let legend_y = MARGIN + timings.data.len() * ROW_HEIGHT + 10;
+    for (i, (name, color)) in PHASES.iter().enumerate() {
+        let x = MARGIN + LABEL_WIDTH + i * 90;
+        lines.push(format!(
+            r#"<rect x="{x}" y="{legend_y}" width="12" height="12" fill="{color}"/><text x="{}" y="{}">{name}</text>"#,
+            x + 16,
+            legend_y + 11
+        ));
+    }
+
+    lines.push("</svg>".into());
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{chart_path, render};
+    use crate::template::timings::{Timing, Timings};
+    use crate::{day, year};
+    use std::path::PathBuf;
+
+    #[test]
+    fn renders_a_bar_per_day() {
+        let timing = |day, total_nanos| Timing {
+            part_1: Some("1ms".into()),
+            part_2: Some("1ms".into()),
+            ..Timing::for_day(day, total_nanos)
+        };
+
+        let svg = render(&Timings {
+            data: vec![timing(day!(1), 1e6), timing(day!(2), 4e6)],
+        });
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains(">Day 1</text>"));
+        assert!(svg.contains(r#"width="120.0""#));
+        assert!(svg.contains(r#"width="480.0""#));
+        assert!(svg.contains(">4.0ms</text>"));
+    }
+
+    #[test]
+    fn stores_chart_per_year() {
+        assert_eq!(
+            chart_path(Some(year!(2023))),
+            PathBuf::from("data/2023/visualizations/benchmarks.svg")
+        );
+        assert_eq!(
+            chart_path(None),
+            PathBuf::from("data/visualizations/benchmarks.svg")
+        );
+    }
+}
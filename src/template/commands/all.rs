@@ -1,5 +1,29 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    Execution, HeapProfiling, InputSource, OutputFormat, Year, all_days, run_multi::run_multi,
+};
+
+pub fn handle(
+    is_release: bool,
+    year: Option<Year>,
+    format: OutputFormat,
+    execution: Execution,
+    input: &InputSource,
+    heap: HeapProfiling,
+) {
+    let summary = run_multi(
+        &all_days().collect(),
+        year,
+        is_release,
+        heap,
+        None,
+        format,
+        execution,
+        input,
+    );
+
+    if summary.has_failures() {
+        process::exit(1);
+    }
 }
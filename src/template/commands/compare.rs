@@ -0,0 +1,47 @@
+/// Compares alternative implementations of the parts of a day, which are declared in its tests with
+/// [`compare_variants`](crate::compare_variants). See [`variants`](crate::template::variants).
+use std::fs;
+use std::process::{self, Stdio};
+use std::time::Duration;
+
+use crate::template::PuzzleId;
+use crate::template::run_multi::child_commands;
+use crate::template::variants::BENCH_TIME_VARIABLE;
+
+pub fn handle(puzzle: PuzzleId, bench_time: Option<Duration>) {
+    let source = fs::read_to_string(puzzle.bin_path()).unwrap_or_default();
+    if !source.contains("compare_variants!") {
+        eprintln!(
+            "No variants to compare in {}. Declare them in its tests with `advent_of_code::compare_variants!`.",
+            puzzle.bin_path()
+        );
+        process::exit(1);
+    }
+
+    let bin_name = puzzle.to_string();
+    let mut cmd = child_commands::cargo_command();
+    cmd.args(["test", "--quiet", "--release", "--bin", &bin_name])
+        // the variants are benched one after another, so that they do not compete for the CPU.
+        .args([
+            "--",
+            "compare_variants",
+            "--ignored",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+
+    if let Some(bench_time) = bench_time {
+        cmd.env(BENCH_TIME_VARIABLE, bench_time.as_millis().to_string());
+    }
+
+    match cmd.status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => process::exit(1),
+        Err(e) => {
+            eprintln!("Failed to run the comparison: {e}");
+            process::exit(1);
+        }
+    }
+}
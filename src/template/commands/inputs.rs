@@ -0,0 +1,256 @@
+/// Runs a day against every input in `data/inputs/DD/`, e.g. the inputs of several accounts, to catch solutions
+/// that only work for the quirks of one input. The known answers of the inputs are declared in
+/// `data/inputs/DD/answers.json`, in the format of the example manifests:
+///
+/// ```json
+/// { "data": [{ "file": "alice.txt", "part_1": "140", "part_2": "80" }] }
+/// ```
+///
+/// Parts are limited like any other run, see `data/limits.json`.
+use std::{fmt::Display, fs, path::PathBuf, process};
+
+use crate::template::commands::verify::{Verdict, verdict};
+use crate::template::examples::ExampleManifest;
+use crate::template::run_multi::child_commands;
+use crate::template::runner::print_report;
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, HeapProfiling, InputSource, OutputFormat, PartReport, Phase, PuzzleId,
+    Status,
+};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// The outcome of one part on one input.
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Verdict(Verdict),
+    /// The part did not finish, e.g. because it timed out.
+    Failed(Status),
+}
+
+impl Outcome {
+    fn new(expected: Option<&str>, report: Option<&PartReport>) -> Self {
+        match report {
+            Some(report) if !report.status.is_ok() => Outcome::Failed(report.status),
+            _ => Outcome::Verdict(verdict(expected, report.and_then(|r| r.result.clone()))),
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Verdict(Verdict::Fail(_))
+                | Outcome::Failed(Status::Panicked | Status::OutOfMemory | Status::Crashed)
+        )
+    }
+
+    fn is_timeout(&self) -> bool {
+        matches!(self, Outcome::Failed(Status::Timeout))
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Verdict(verdict) => write!(f, "{verdict}"),
+            Outcome::Failed(status) => write!(f, "{status}"),
+        }
+    }
+}
+
+/// The input files of a day, ordered by name.
+fn input_files(puzzle: PuzzleId) -> Result<Vec<PathBuf>, String> {
+    let dir = puzzle.data_path("inputs", "");
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| format!("could not open inputs folder {}: {e}", dir.display()))?;
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        return Err(format!("no `.txt` inputs in {}.", dir.display()));
+    }
+
+    Ok(files)
+}
+
+/// Joins the names of the inputs whose outcomes match a predicate, e.g. `alice.txt, bob.txt`.
+fn describe_inputs(
+    summary: &[(String, [Outcome; 2])],
+    predicate: impl Fn(&Outcome) -> bool,
+) -> Option<String> {
+    let names: Vec<&str> = summary
+        .iter()
+        .filter(|(_, outcomes)| outcomes.iter().any(&predicate))
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    (!names.is_empty()).then(|| names.join(", "))
+}
+
+pub fn handle(puzzle: PuzzleId, is_release: bool) {
+    let files = input_files(puzzle).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let answers_path = puzzle.data_path("inputs", "").join(ANSWERS_FILE_NAME);
+    let answers = if answers_path.exists() {
+        ExampleManifest::read_from_path(&answers_path).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    } else {
+        eprintln!("No known answers in {}.", answers_path.display());
+        ExampleManifest::default()
+    };
+
+    let mut summary: Vec<(String, [Outcome; 2])> = vec![];
+
+    for path in files {
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        if !summary.is_empty() {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}{name}{ANSI_RESET}");
+        println!("------");
+
+        let mut reports = vec![];
+        let input = InputSource::File(path);
+        let result = child_commands::run_solution(
+            puzzle,
+            None,
+            is_release,
+            HeapProfiling::Off,
+            &input,
+            |report| {
+                print_report(&report, OutputFormat::Text);
+                reports.push(report);
+            },
+        );
+
+        if let Err(e) = result {
+            eprintln!("Failed to run solution: {e}");
+        }
+
+        let known = answers.for_file(&name);
+        let outcome = |part: u8| {
+            Outcome::new(
+                known.and_then(|known| known.expected(part)),
+                reports.iter().find(|r| r.phase == Phase::Part(part)),
+            )
+        };
+
+        summary.push((name, [outcome(1), outcome(2)]));
+    }
+
+    println!("\n{ANSI_BOLD}Inputs{ANSI_RESET}");
+    println!("------");
+
+    for (name, [part_1, part_2]) in &summary {
+        println!("{name}: Part 1 {part_1}, Part 2 {part_2}");
+    }
+
+    let failed = describe_inputs(&summary, Outcome::is_failure);
+    let timed_out = describe_inputs(&summary, Outcome::is_timeout);
+
+    if let Some(failed) = &failed {
+        eprintln!("\nFailed: {failed}");
+    }
+    if let Some(timed_out) = &timed_out {
+        eprintln!("\nTimed out: {timed_out}");
+    }
+
+    if failed.is_some() || timed_out.is_some() {
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Outcome, describe_inputs};
+    use crate::day;
+    use crate::template::commands::verify::Verdict;
+    use crate::template::{PartReport, Phase, Status};
+    use std::time::Duration;
+
+    fn report(result: Option<&str>, status: Status) -> PartReport {
+        PartReport {
+            puzzle: day!(17).into(),
+            phase: Phase::Part(2),
+            status,
+            result: result.map(Into::into),
+            error: None,
+            duration: Duration::from_millis(1),
+            stats: None,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn checks_outcomes() {
+        let pass = report(Some("42"), Status::Ok);
+        let timeout = report(None, Status::Timeout);
+
+        assert_eq!(
+            Outcome::new(Some("42"), Some(&pass)),
+            Outcome::Verdict(Verdict::Pass)
+        );
+        assert_eq!(
+            Outcome::new(Some("41"), Some(&pass)),
+            Outcome::Verdict(Verdict::Fail(Some("42".into())))
+        );
+        assert_eq!(
+            Outcome::new(None, Some(&pass)),
+            Outcome::Verdict(Verdict::Missing)
+        );
+        assert_eq!(
+            Outcome::new(Some("42"), None),
+            Outcome::Verdict(Verdict::Fail(None))
+        );
+        assert_eq!(
+            Outcome::new(Some("42"), Some(&timeout)),
+            Outcome::Failed(Status::Timeout)
+        );
+    }
+
+    #[test]
+    fn summarizes_failing_inputs() {
+        let summary = vec![
+            (
+                "alice.txt".to_string(),
+                [
+                    Outcome::Verdict(Verdict::Pass),
+                    Outcome::Verdict(Verdict::Pass),
+                ],
+            ),
+            (
+                "bob.txt".to_string(),
+                [
+                    Outcome::Verdict(Verdict::Pass),
+                    Outcome::Verdict(Verdict::Fail(None)),
+                ],
+            ),
+            (
+                "carol.txt".to_string(),
+                [
+                    Outcome::Failed(Status::Timeout),
+                    Outcome::Verdict(Verdict::Missing),
+                ],
+            ),
+        ];
+
+        assert_eq!(
+            describe_inputs(&summary, Outcome::is_failure),
+            Some("bob.txt".into())
+        );
+        assert_eq!(
+            describe_inputs(&summary, Outcome::is_timeout),
+            Some("carol.txt".into())
+        );
+    }
+}
@@ -1,6 +1,10 @@
 pub mod all;
+pub mod compare;
 pub mod download;
+pub mod inputs;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
+pub mod watch;
@@ -1,13 +1,80 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::examples::{Example, ExampleManifest};
+use crate::template::{PuzzleId, aoc_client};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Templates are read at runtime from this directory, e.g. `templates/grid.txt` for `--template grid`.
+const TEMPLATES_DIR: &str = "templates";
+const DEFAULT_TEMPLATE_NAME: &str = "default";
+
+/// Used if the default template is missing from the templates directory.
+const DEFAULT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.txt"
+));
+
+/// Reads a template by name from the templates directory.
+fn read_template(name: &str) -> Result<String, String> {
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name == DEFAULT_TEMPLATE_NAME => Ok(DEFAULT_TEMPLATE.to_string()),
+        Err(e) => Err(format!(
+            "Failed to read template \"{}\": {e}. Available templates: {}",
+            path.display(),
+            available_templates().join(", ")
+        )),
+    }
+}
+
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            (path.extension()? == "txt").then_some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+
+    if !names.iter().any(|name| name == DEFAULT_TEMPLATE_NAME) {
+        names.push(DEFAULT_TEMPLATE_NAME.into());
+    }
+
+    names.sort();
+    names
+}
+
+/// The title of a puzzle from its downloaded description, e.g. `Day 5: Print Queue`.
+/// Falls back to the day if the puzzle was not downloaded.
+fn puzzle_title(puzzle: PuzzleId) -> String {
+    fs::read_to_string(puzzle.data_path("puzzles", ".md"))
+        .ok()
+        .and_then(|text| title_from_markdown(&text))
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()))
+}
+
+fn title_from_markdown(text: &str) -> Option<String> {
+    text.lines()
+        .find_map(|line| line.trim_start_matches('#').trim().strip_prefix("--- "))
+        .map(|title| title.trim_end_matches("---").trim().to_string())
+}
+
+/// Replaces the placeholders of a template, e.g. `%DAY%` in `// Day %DAY%`.
+fn render(template: &str, placeholders: &[(&str, &str)]) -> String {
+    placeholders
+        .iter()
+        .fold(template.to_string(), |text, (placeholder, value)| {
+            text.replace(&format!("%{placeholder}%"), value)
+        })
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,18 +86,57 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+/// Creates an empty file unless it exists, e.g. because the input was downloaded before scaffolding.
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
-        .truncate(true)
+        .truncate(false)
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates an empty example and a manifest that declares it without answers, so that the generated tests
+/// fail until the answers are filled in rather than checking nothing.
+fn create_example(puzzle: PuzzleId, example_path: &Path) -> Result<(), std::io::Error> {
+    create_file(example_path)?;
+
+    let manifest = ExampleManifest {
+        data: vec![Example {
+            file: format!("{}.txt", puzzle.day),
+            part_1: None,
+            part_2: None,
+        }],
+    };
+    manifest.store_file(puzzle)
+}
+
+/// Creates the module of a puzzle from a template, and empty input and example files.
+///
+/// Templates can use the placeholders `%DAY_NUMBER%` (the arguments of `solution!`), `%YEAR%`, `%DAY%`,
+/// `%TITLE%` and `%URL%`. Their tests check the examples declared in the example manifest with
+/// [`example_tests`](crate::example_tests), so a puzzle without a downloaded manifest gets one to fill in.
+pub fn handle(puzzle: PuzzleId, overwrite: bool, template: Option<&str>) {
+    let template = match read_template(template.unwrap_or(DEFAULT_TEMPLATE_NAME)) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = puzzle.data_path("inputs", ".txt");
+    let example_path = puzzle.data_path("examples", ".txt");
+    let manifest_path = puzzle.data_path("examples", ".json");
+    let module_path = puzzle.bin_path();
+
+    // year-scoped puzzles are declared as `solution!(2024, 17)`.
+    let solution_args = match puzzle.year {
+        Some(year) => format!("{year}, {}", puzzle.day.into_inner()),
+        None => puzzle.day.into_inner().to_string(),
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +146,28 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let year = puzzle
+        .year_or_env()
+        .map(|year| year.to_string())
+        .unwrap_or_default();
+    let day = puzzle.day.to_string();
+    let title = puzzle_title(puzzle);
+    let url = aoc_client::puzzle_url(puzzle).unwrap_or_default();
+
+    let module = render(
+        &template,
+        &[
+            ("DAY_NUMBER", &solution_args),
+            ("YEAR", &year),
+            ("DAY", &day),
+            ("TITLE", &title),
+            ("URL", &url),
+        ],
+    );
+
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{module_path}\"");
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -54,26 +175,62 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    if input_path.exists() {
+        println!("Using existing input file \"{}\"", input_path.display());
+    } else {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", input_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    // examples extracted from the puzzle page are declared in the manifest already.
+    if !manifest_path.exists() {
+        match create_example(puzzle, &example_path) {
+            Ok(()) => {
+                println!(
+                    "Created empty example file \"{}\", declare its answers in \"{}\"",
+                    example_path.display(),
+                    manifest_path.display()
+                );
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {puzzle}` to run your solution.");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, title_from_markdown};
+
+    #[test]
+    fn reads_titles_from_puzzles() {
+        assert_eq!(
+            title_from_markdown("## --- Day 5: Print Queue ---\n\nSatisfied...").as_deref(),
+            Some("Day 5: Print Queue")
+        );
+        assert_eq!(title_from_markdown("No title"), None);
+    }
+
+    #[test]
+    fn replaces_placeholders() {
+        assert_eq!(
+            render(
+                "// %TITLE%, %DAY%\nsolution!(%DAY_NUMBER%);",
+                &[("DAY_NUMBER", "2024, 5"), ("DAY", "05"), ("TITLE", "Day 5")]
+            ),
+            "// Day 5, 05\nsolution!(2024, 5);"
+        );
+    }
 }
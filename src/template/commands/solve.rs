@@ -1,20 +1,23 @@
-use std::process::{Command, Stdio};
-
-use crate::template::Day;
-
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
-    }
+use std::process::Stdio;
+
+use crate::template::report::CSV_HEADER;
+use crate::template::run_multi::child_commands;
+use crate::template::{HeapProfiling, InputSource, OutputFormat, PuzzleId};
+
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    heap: HeapProfiling,
+    submit_part: Option<u8>,
+    format: OutputFormat,
+    input: &InputSource,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
+    cmd_args.extend(
+        child_commands::profile_args(release, heap)
+            .into_iter()
+            .map(String::from),
+    );
 
     cmd_args.push("--".to_string());
 
@@ -23,7 +26,18 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    cmd_args.extend(input.args());
+
+    if format != OutputFormat::Text {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    if format == OutputFormat::Csv {
+        println!("{CSV_HEADER}");
+    }
+
+    let mut cmd = child_commands::cargo_command()
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
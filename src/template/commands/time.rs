@@ -1,11 +1,45 @@
 use std::collections::HashSet;
+use std::fmt::Display;
+use std::process;
+use std::time::Duration;
 
+use crate::template::answers::Answers;
+use crate::template::bench_history::{self, BenchHistory, BenchRun, Comparison};
 use crate::template::run_multi::run_multi;
+use crate::template::runner::DEFAULT_BENCH_TIME;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Columns, Day, Execution, HeapProfiling, InputSource,
+    OutputFormat, Year, all_days, readme_benchmarks, readme_stars,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Phases that got slower by more than this many percent are flagged by `--compare`.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Options of `time --compare`.
+pub struct CompareOptions {
+    /// Name or commit of the stored run to compare against. Defaults to the previous run of each day.
+    pub baseline: Option<String>,
+    /// Threshold in percent above which a slower phase counts as a regression.
+    pub threshold: Option<f64>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    name: Option<String>,
+    compare: Option<CompareOptions>,
+    year: Option<Year>,
+    bench_time: Option<Duration>,
+    format: OutputFormat,
+    execution: Execution,
+    columns: &Columns,
+    heap: HeapProfiling,
+) {
+    let stored_timings = Timings::read_from_file(year);
+    let mut history = BenchHistory::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,20 +55,142 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if heap == HeapProfiling::Dhat && format == OutputFormat::Text {
+        println!(
+            "{ANSI_ITALIC}Profiling heap usage, timings include the overhead of the profiler.{ANSI_RESET}\n"
+        );
+    }
+
+    let summary = run_multi(
+        &days_to_run,
+        year,
+        true,
+        heap,
+        Some(bench_time.unwrap_or(DEFAULT_BENCH_TIME)),
+        format,
+        execution,
+        // benchmarks are only meaningful for the puzzle inputs.
+        &InputSource::Puzzle,
+    );
+    let has_failures = summary.has_failures();
+    let timings = summary.timings.unwrap();
+
+    // compare before storing, so that the current run does not end up as its own baseline.
+    let has_regressions =
+        compare.is_some_and(|options| print_comparison(&history, &timings, &options, format));
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
+
+        history.push(BenchRun::now(timings, name));
+        history.store_file(year).unwrap();
+
+        print_line(format, "");
+        match readme_stars::update(&merged_timings, &Answers::read_from_file(year), year) {
+            Ok(()) => {
+                print_line(format, "Stored updated stars.");
+            }
+            Err(_) => {
+                eprintln!("Failed to store updated stars.");
+            }
+        }
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, year, columns) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                print_line(format, "Stored updated benchmarks.");
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    if has_regressions || has_failures {
+        process::exit(1);
+    }
+}
+
+/// Prints a line that is not part of the reports: to stdout in text output, to stderr otherwise, so that the
+/// reports of `--format json` and `--format csv` stay machine-readable.
+fn print_line(format: OutputFormat, line: impl Display) {
+    if format == OutputFormat::Text {
+        println!("{line}");
+    } else {
+        eprintln!("{line}");
+    }
+}
+
+/// Prints the change of every phase against the baseline. Returns whether any phase regressed.
+fn print_comparison(
+    history: &BenchHistory,
+    timings: &Timings,
+    options: &CompareOptions,
+    format: OutputFormat,
+) -> bool {
+    let threshold = options.threshold.unwrap_or(DEFAULT_THRESHOLD);
+    let print = |line: String| print_line(format, line);
+
+    print(String::new());
+    let Some(baseline) = history.baseline(options.baseline.as_deref()) else {
+        match &options.baseline {
+            Some(name) => eprintln!("No stored benchmark run named `{name}`."),
+            None => eprintln!("No stored benchmark runs to compare against."),
+        }
+        return false;
+    };
+
+    match options
+        .baseline
+        .as_deref()
+        .and_then(|name| history.find(name))
+    {
+        Some(run) => print(format!(
+            "{ANSI_BOLD}Compared to {}{ANSI_RESET}",
+            run.label()
+        )),
+        None => print(format!("{ANSI_BOLD}Compared to previous run{ANSI_RESET}")),
+    }
+    print("------".into());
+
+    let comparisons = bench_history::compare(&baseline, timings);
+    if comparisons.is_empty() {
+        print("No timed phases in common with the baseline.".into());
+    }
+
+    for comparison in &comparisons {
+        print(format_comparison(comparison, threshold));
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count();
+
+    if regressions > 0 {
+        print(format!(
+            "\n{regressions} phase(s) got slower by more than {threshold}%."
+        ));
+    }
+
+    regressions > 0
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_comparison(comparison: &Comparison, threshold: f64) -> String {
+    let duration = |nanos: f64| Duration::from_nanos(nanos as u64);
+    let flag = if comparison.is_regression(threshold) {
+        " ⚠ slower"
+    } else {
+        ""
+    };
+
+    format!(
+        "Day {} {}: {:.1?} → {:.1?} ({:+.1}%){flag}",
+        comparison.day,
+        comparison.phase,
+        duration(comparison.baseline_nanos),
+        duration(comparison.current_nanos),
+        comparison.change_percent()
+    )
 }
@@ -0,0 +1,115 @@
+use std::{fmt::Display, path::Path, process};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::child_commands;
+use crate::template::runner::print_report;
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, HeapProfiling, InputSource, OutputFormat, PuzzleId, Year, all_days,
+};
+
+/// The result of comparing one part against its recorded correct answer.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Verdict {
+    Pass,
+    Fail(Option<String>),
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(Some(result)) => write!(f, "FAIL (got `{result}`)"),
+            Verdict::Fail(None) => write!(f, "FAIL (no result)"),
+            Verdict::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+pub(crate) fn verdict(expected: Option<&str>, actual: Option<String>) -> Verdict {
+    match expected {
+        None => Verdict::Missing,
+        Some(expected) if actual.as_deref() == Some(expected) => Verdict::Pass,
+        Some(_) => Verdict::Fail(actual),
+    }
+}
+
+/// Runs every scaffolded day against its real input and compares each part with the answer
+/// recorded as correct in the answer ledger. Exits with a non-zero status on any mismatch.
+pub fn handle(is_release: bool, year: Option<Year>) {
+    let answers = Answers::read_from_file(year);
+    let mut summary: Vec<(Day, [Verdict; 2])> = vec![];
+
+    let puzzles = all_days().map(|day| PuzzleId::new(year, day));
+
+    for puzzle in puzzles.filter(|puzzle| Path::new(&puzzle.bin_path()).exists()) {
+        let day = puzzle.day;
+        if !summary.is_empty() {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let mut reports = vec![];
+        let result = child_commands::run_solution(
+            puzzle,
+            None,
+            is_release,
+            HeapProfiling::Off,
+            &InputSource::Puzzle,
+            |report| {
+                print_report(&report, OutputFormat::Text);
+                reports.push(report);
+            },
+        );
+
+        if let Err(e) = result {
+            eprintln!("Failed to run solution: {e}");
+        }
+
+        let [part_1, part_2] = child_commands::results_from_reports(&reports);
+
+        summary.push((
+            day,
+            [
+                verdict(answers.correct_answer(day, 1), part_1),
+                verdict(answers.correct_answer(day, 2), part_2),
+            ],
+        ));
+    }
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    for (day, [part_1, part_2]) in &summary {
+        println!("Day {day}: Part 1 {part_1}, Part 2 {part_2}");
+    }
+
+    let failures = summary
+        .iter()
+        .flat_map(|(_, verdicts)| verdicts)
+        .filter(|v| matches!(v, Verdict::Fail(_)))
+        .count();
+
+    if failures > 0 {
+        eprintln!("\n{failures} part(s) no longer produce their recorded answer.");
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Verdict, verdict};
+
+    #[test]
+    fn compares_results() {
+        assert_eq!(verdict(Some("42"), Some("42".into())), Verdict::Pass);
+        assert_eq!(
+            verdict(Some("42"), Some("41".into())),
+            Verdict::Fail(Some("41".into()))
+        );
+        assert_eq!(verdict(Some("42"), None), Verdict::Fail(None));
+        assert_eq!(verdict(None, Some("42".into())), Verdict::Missing);
+    }
+}
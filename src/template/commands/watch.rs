@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::run_multi::child_commands;
+use crate::template::runner::print_report;
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, HeapProfiling, InputSource, OutputFormat, PuzzleId,
+    data_dir,
+};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Clears the terminal and moves the cursor to the top left.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Reruns the tests and the solution of a puzzle whenever its bin file, examples or input change,
+/// and shows how the results differ from the previous run.
+pub fn handle(puzzle: PuzzleId, release: bool) {
+    let mut snapshot = vec![];
+    let mut previous: Option<[Option<String>; 2]> = None;
+
+    loop {
+        let current = watched_files(puzzle);
+
+        if current != snapshot {
+            snapshot = current;
+            print!("{CLEAR_SCREEN}");
+
+            run_tests(puzzle, release);
+            let results = run_solution(puzzle, release);
+
+            if let Some(previous) = &previous {
+                println!("\n{ANSI_BOLD}Changes{ANSI_RESET}");
+                println!("------");
+                for (i, (before, after)) in previous.iter().zip(&results).enumerate() {
+                    println!(
+                        "{}",
+                        describe_change(i + 1, before.as_deref(), after.as_deref())
+                    );
+                }
+            }
+
+            previous = Some(results);
+
+            let files: Vec<String> = snapshot
+                .iter()
+                .map(|(p, _)| p.display().to_string())
+                .collect();
+            println!(
+                "\n{ANSI_ITALIC}Watching {}. Press Ctrl-C to stop.{ANSI_RESET}",
+                files.join(", ")
+            );
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn run_tests(puzzle: PuzzleId, release: bool) {
+    println!("{ANSI_BOLD}Tests{ANSI_RESET}");
+    println!("------");
+
+    let bin_name = puzzle.to_string();
+    let mut args = vec!["test", "--quiet", "--bin", &bin_name];
+
+    if release {
+        args.push("--release");
+    }
+
+    let status = child_commands::cargo_command()
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    if let Err(e) = status {
+        eprintln!("Failed to run tests: {e}");
+    }
+}
+
+/// Runs the solution on its input and returns the results of both parts.
+fn run_solution(puzzle: PuzzleId, release: bool) -> [Option<String>; 2] {
+    println!("\n{ANSI_BOLD}Solution{ANSI_RESET}");
+    println!("------");
+
+    let mut reports = vec![];
+
+    let result = child_commands::run_solution(
+        puzzle,
+        None,
+        release,
+        HeapProfiling::Off,
+        &InputSource::Puzzle,
+        |report| {
+            print_report(&report, OutputFormat::Text);
+            reports.push(report);
+        },
+    );
+
+    if let Err(e) = result {
+        eprintln!("Failed to run solution: {e}");
+    }
+
+    child_commands::results_from_reports(&reports)
+}
+
+/// Returns the watched files of a puzzle with the time they were last modified: the bin file, the
+/// input, the example manifest and every example, e.g. `data/examples/08.txt` and `data/examples/08-2.txt`.
+fn watched_files(puzzle: PuzzleId) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let examples_dir = data_dir(puzzle.year).join("examples");
+
+    let mut paths = vec![
+        PathBuf::from(puzzle.bin_path()),
+        puzzle.data_path("inputs", ".txt"),
+        puzzle.data_path("examples", ".json"),
+    ];
+
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        let mut examples: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| is_example_of(name, puzzle))
+            })
+            .collect();
+
+        examples.sort();
+        paths.extend(examples);
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Whether a file name is an example of the puzzle, i.e. `DD.txt` or `DD-<suffix>.txt`.
+fn is_example_of(name: &str, puzzle: PuzzleId) -> bool {
+    let Some(rest) = name.strip_prefix(&puzzle.day.to_string()) else {
+        return false;
+    };
+
+    rest == ".txt" || (rest.starts_with('-') && rest.ends_with(".txt"))
+}
+
+/// Describes how the result of a part changed between two runs.
+fn describe_change(part: usize, before: Option<&str>, after: Option<&str>) -> String {
+    let show = |result: Option<&str>| result.unwrap_or("✖").to_string();
+
+    if before == after {
+        return format!("Part {part}: unchanged");
+    }
+
+    let (before, after) = (show(before), show(after));
+
+    if before.contains('\n') || after.contains('\n') {
+        format!("Part {part}: changed from\n{before}\nto\n{after}")
+    } else {
+        format!("Part {part}: {before} → {ANSI_BOLD}{after}{ANSI_RESET}")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{describe_change, is_example_of};
+    use crate::day;
+    use crate::template::{ANSI_BOLD, ANSI_RESET, PuzzleId};
+
+    #[test]
+    fn matches_examples_of_a_day() {
+        let puzzle = PuzzleId::new(None, day!(8));
+        assert!(is_example_of("08.txt", puzzle));
+        assert!(is_example_of("08-2.txt", puzzle));
+        assert!(!is_example_of("18.txt", puzzle));
+        assert!(!is_example_of("080.txt", puzzle));
+        assert!(!is_example_of("08-2.md", puzzle));
+    }
+
+    #[test]
+    fn describes_changes() {
+        assert_eq!(
+            describe_change(1, Some("42"), Some("42")),
+            "Part 1: unchanged"
+        );
+        assert_eq!(
+            describe_change(2, None, Some("42")),
+            format!("Part 2: ✖ → {ANSI_BOLD}42{ANSI_RESET}")
+        );
+        assert_eq!(
+            describe_change(1, Some("#.\n.#"), Some("..\n##")),
+            "Part 1: changed from\n#.\n.#\nto\n..\n##"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+/// A global allocator that counts the allocations of each thread, installed with the `count-allocs` feature.
+/// Unlike dhat, it needs no build profile of its own and only adds a few counter updates per allocation, so
+/// the heap usage of every run can be reported, even while benching.
+///
+/// Runs happen on threads of their own, see [`runner`](crate::template::runner), so the counters of a run
+/// thread are the heap usage of its part. Allocations of threads that a part spawns itself are not counted.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use crate::template::memory::MemoryStats;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+pub struct CountingAlloc;
+
+/// Allocation counters of a thread.
+struct Counters {
+    allocations: Cell<u64>,
+    total_bytes: Cell<u64>,
+    /// Bytes allocated minus bytes freed, negative if the thread freed memory of other threads.
+    current_bytes: Cell<i64>,
+    peak_bytes: Cell<i64>,
+}
+
+thread_local! {
+    // const-initialized without a destructor, so that accessing it never allocates.
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            total_bytes: Cell::new(0),
+            current_bytes: Cell::new(0),
+            peak_bytes: Cell::new(0),
+        }
+    };
+}
+
+/// Resets the counters of the current thread.
+pub fn reset() {
+    COUNTERS.with(|c| {
+        c.allocations.set(0);
+        c.total_bytes.set(0);
+        c.current_bytes.set(0);
+        c.peak_bytes.set(0);
+    });
+}
+
+/// Heap usage of the current thread since the last [`reset`].
+#[allow(clippy::cast_sign_loss)]
+pub fn stats() -> MemoryStats {
+    COUNTERS.with(|c| MemoryStats {
+        peak_bytes: c.peak_bytes.get().max(0) as u64,
+        allocations: c.allocations.get(),
+        total_bytes: c.total_bytes.get(),
+    })
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn record_alloc(size: usize) {
+    // the counters are gone while the thread is torn down, its allocations are not part of a run then.
+    let _ = COUNTERS.try_with(|c| {
+        c.allocations.set(c.allocations.get() + 1);
+        c.total_bytes.set(c.total_bytes.get() + size as u64);
+        c.current_bytes.set(c.current_bytes.get() + size as i64);
+        c.peak_bytes
+            .set(c.peak_bytes.get().max(c.current_bytes.get()));
+    });
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn record_dealloc(size: usize) {
+    let _ = COUNTERS.try_with(|c| c.current_bytes.set(c.current_bytes.get() - size as i64));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    /// Counts like dhat: a reallocation frees the old block and allocates a new one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{reset, stats};
+    use std::hint::black_box;
+    use std::thread;
+
+    #[test]
+    fn counts_allocations_per_thread() {
+        let usage = thread::spawn(|| {
+            reset();
+            let mut numbers: Vec<u64> = Vec::with_capacity(128);
+            numbers.extend(0..128);
+            drop(black_box(numbers));
+            let boxed = black_box(Box::new([0_u8; 512]));
+            drop(boxed);
+            stats()
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(usage.allocations, 2);
+        assert_eq!(usage.total_bytes, 1024 + 512);
+        assert_eq!(usage.peak_bytes, 1024);
+    }
+
+    #[test]
+    fn tracks_reallocations() {
+        let usage = thread::spawn(|| {
+            reset();
+            let mut bytes: Vec<u8> = Vec::with_capacity(100);
+            bytes.reserve_exact(300);
+            let kept = black_box(bytes);
+            let usage = stats();
+            drop(kept);
+            usage
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(usage.allocations, 2);
+        assert_eq!(usage.total_bytes, 100 + 300);
+        assert_eq!(usage.peak_bytes, 300);
+    }
+}
@@ -0,0 +1,455 @@
+/// Expected answers of the examples of a day, declared next to the examples in `data/examples/DD.json`:
+///
+/// ```json
+/// {
+///   "data": [
+///     { "file": "12-1.txt", "part_1": "140", "part_2": "80" },
+///     { "file": "12-3.txt", "part_2": "236" }
+///   ]
+/// }
+/// ```
+///
+/// Answers are compared by their string representation, so that every answer type can be declared.
+/// The tests generated by [`example_tests`](crate::example_tests) check every example with an answer.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::runner::PartResult;
+use crate::template::{PuzzleId, data_dir};
+
+/// An example input and the answers it is expected to give.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    /// File name of the example in the examples folder, e.g. `12-1.txt`.
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// The examples of a day.
+/// Can be deserialized from JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExampleManifest {
+    pub data: Vec<Example>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl ExampleManifest {
+    /// Dehydrate the examples of a puzzle to its manifest.
+    pub fn store_file(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(puzzle.data_path("examples", ".json"))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the examples of a puzzle from its manifest.
+    pub fn read_from_file(puzzle: PuzzleId) -> Result<Self, String> {
+        ExampleManifest::read_from_path(&puzzle.data_path("examples", ".json"))
+    }
+
+    /// Rehydrate a manifest from a path, e.g. the known answers of the inputs in `data/inputs/DD/answers.json`.
+    pub fn read_from_path(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path)
+            .map_err(|e| format!("could not open example manifest {}: {e}", path.display()))?;
+
+        ExampleManifest::try_from(s)
+    }
+
+    /// The example declared for a file.
+    pub fn for_file(&self, file: &str) -> Option<&Example> {
+        self.data.iter().find(|example| example.file == file)
+    }
+
+    /// The first example that declares an answer for a part.
+    pub fn for_part(&self, part: u8) -> Option<(&Example, &str)> {
+        self.data
+            .iter()
+            .find_map(|example| Some((example, example.expected(part)?)))
+    }
+
+    /// Declares the answer of an example for a part, unless the example already declares one.
+    fn declare(&mut self, file: &str, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|e| e.file == file) {
+            Some(index) => index,
+            None => {
+                self.data.push(Example {
+                    file: file.into(),
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.len() - 1
+            }
+        };
+
+        let expected = match part {
+            1 => &mut self.data[index].part_1,
+            _ => &mut self.data[index].part_2,
+        };
+
+        expected.get_or_insert_with(|| answer.into());
+    }
+}
+
+/// The examples of a puzzle page: its code blocks, and for every part the block its answer belongs to.
+#[derive(Debug, Default, PartialEq)]
+pub struct PageExamples {
+    pub blocks: Vec<String>,
+    /// The part, the index of a block in `blocks` and the highlighted answer, for every part on the page that
+    /// has one.
+    pub answers: Vec<(u8, usize, String)>,
+}
+
+impl PageExamples {
+    /// Extracts the `<pre><code>` blocks of a puzzle page, and the answer of every part.
+    ///
+    /// The answer of a part is the last highlighted `<code><em>` of its `<article>`, which belongs to the
+    /// last block before it. Part two usually reuses the example of part one, then the block is the last one
+    /// of part one. Parts are numbered by their article, so that a part without an answer does not shift the
+    /// answers of the parts after it.
+    pub fn from_html(html: &str) -> Self {
+        let mut examples = PageExamples::default();
+
+        for (part, article) in (1..).zip(tag_contents(html, "<article", "</article>")) {
+            let mut blocks = vec![];
+            for (start, block) in tag_positions(article, "<pre><code>", "</code></pre>") {
+                blocks.push(start);
+                examples.blocks.push(html_text(block));
+            }
+
+            let Some((answer_start, answer)) =
+                tag_positions(article, "<code><em>", "</em></code>").pop()
+            else {
+                continue;
+            };
+
+            let preceding = blocks.iter().filter(|start| **start < answer_start).count();
+            let block = examples.blocks.len() - blocks.len() + preceding;
+
+            if let Some(block) = block.checked_sub(1) {
+                examples.answers.push((part, block, html_text(answer)));
+            }
+        }
+
+        examples
+    }
+
+    /// Writes the blocks to `data/examples/DD-N.txt` and declares the answers in the manifest of the puzzle.
+    /// Existing example files and declared answers are kept. Returns the paths of the written examples.
+    pub fn store(&self, puzzle: PuzzleId) -> Result<Vec<PathBuf>, Error> {
+        let mut written = vec![];
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            let path = puzzle.data_path("examples", &format!("-{}.txt", i + 1));
+            if !path.exists() {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&path, block)?;
+                written.push(path);
+            }
+        }
+
+        if !self.answers.is_empty() {
+            let mut manifest = ExampleManifest::read_from_file(puzzle).unwrap_or_default();
+
+            for (part, block, answer) in &self.answers {
+                manifest.declare(&format!("{}-{}.txt", puzzle.day, block + 1), *part, answer);
+            }
+
+            manifest.store_file(puzzle)?;
+        }
+
+        Ok(written)
+    }
+}
+
+/// The contents of all elements between an opening and a closing tag.
+fn tag_contents<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    tag_positions(html, open, close)
+        .into_iter()
+        .map(|(_, contents)| contents)
+        .collect()
+}
+
+/// The contents of all elements between an opening and a closing tag, with the position they start at.
+fn tag_positions<'a>(html: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut found = vec![];
+    let mut offset = 0;
+
+    while let Some(start) = html[offset..].find(open).map(|i| offset + i) {
+        // skip the attributes of the opening tag, e.g. `<article class="day-desc">`.
+        let Some(contents) = html[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = html[contents..].find(close).map(|i| contents + i) else {
+            break;
+        };
+
+        found.push((start, &html[contents..end]));
+        offset = end + close.len();
+    }
+
+    found
+}
+
+/// The text of an HTML fragment, without tags and with entities decoded.
+fn html_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Runs a part on every example of a puzzle that declares an answer for it, and panics with a list of all
+/// examples that gave a different answer. Panics as well if no example declares an answer for the part.
+#[track_caller]
+pub fn check_examples<R: PartResult>(
+    puzzle: impl Into<PuzzleId>,
+    part: u8,
+    solve: impl Fn(&str) -> R,
+) {
+    let puzzle = puzzle.into();
+    let manifest = ExampleManifest::read_from_file(puzzle).unwrap_or_else(|e| panic!("{e}"));
+
+    let examples: Vec<(&Example, &str)> = manifest
+        .data
+        .iter()
+        .filter_map(|example| Some((example, example.expected(part)?)))
+        .collect();
+
+    assert!(
+        !examples.is_empty(),
+        "no example of day {} declares an answer for part {part}.",
+        puzzle.day
+    );
+
+    let mismatches: Vec<String> = examples
+        .into_iter()
+        .filter_map(|(example, expected)| {
+            let path = data_dir(puzzle.year).join("examples").join(&example.file);
+            let input = fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("could not open example {}: {e}", path.display()));
+
+            let actual = match solve(&input).answer() {
+                Ok(answer) => answer.to_string(),
+                Err(Some(error)) => format!("✖ ({error})"),
+                Err(None) => "✖".into(),
+            };
+
+            (actual != expected)
+                .then(|| format!("{}: expected `{expected}`, got `{actual}`", example.file))
+        })
+        .collect();
+
+    assert!(
+        mismatches.is_empty(),
+        "part {part} gave wrong answers for examples:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<ExampleManifest> for JsonValue {
+    fn from(value: ExampleManifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for ExampleManifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(ExampleManifest {
+            data: json_data
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Example> for JsonValue {
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+
+        if let Some(answer) = &value.part_1 {
+            map.insert("part_1".into(), JsonValue::String(answer.clone()));
+        }
+        if let Some(answer) = &value.part_2 {
+            map.insert("part_2".into(), JsonValue::String(answer.clone()));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?;
+
+        let answer = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .map(|answer| Some(answer.clone()))
+                .ok_or(format!("Expected example.{key} to be a string.")),
+        };
+
+        Ok(Example {
+            file: file.clone(),
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, ExampleManifest, PageExamples};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = ExampleManifest::try_from(
+            r#"{
+                "data": [
+                    { "file": "12-1.txt", "part_1": "140", "part_2": "80" },
+                    { "file": "12-3.txt", "part_2": "236" }
+                ]
+            }"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.data[1],
+            Example {
+                file: "12-3.txt".into(),
+                part_1: None,
+                part_2: Some("236".into()),
+            }
+        );
+        assert_eq!(manifest.data[0].expected(1), Some("140"));
+        assert_eq!(manifest.data[1].expected(1), None);
+    }
+
+    #[test]
+    fn rejects_numeric_answers() {
+        let manifest = ExampleManifest::try_from(
+            r#"{ "data": [{ "file": "01.txt", "part_1": 11 }] }"#.to_string(),
+        );
+        assert!(manifest.is_err());
+    }
+
+    #[test]
+    fn roundtrips_manifests() {
+        let mut manifest = ExampleManifest::default();
+        manifest.declare("12-1.txt", 1, "140");
+        manifest.declare("12-1.txt", 2, "80");
+        manifest.declare("12-1.txt", 1, "141");
+
+        let json = JsonValue::from(manifest.clone()).stringify().unwrap();
+        assert_eq!(ExampleManifest::try_from(json).unwrap(), manifest);
+        assert_eq!(manifest.for_part(1).map(|(_, answer)| answer), Some("140"));
+    }
+
+    #[test]
+    fn extracts_examples_from_puzzle_pages() {
+        let html = r#"<main>
+            <article class="day-desc"><h2>--- Day 1 ---</h2>
+            <p>For example:</p><pre><code>1 &lt; 2
+<em>3</em>
+</code></pre><p>Pairs:</p><pre><code>1 2
+3 4
+</code></pre>
+            <p>The distance is <code><em>1</em></code>, the total <code><em>11</em></code>.</p></article>
+            <p>Your puzzle answer was <code>1234</code>.</p>
+            <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+            <p>The similarity is <code><em>31</em></code>.</p></article>
+        </main>"#;
+
+        assert_eq!(
+            PageExamples::from_html(html),
+            PageExamples {
+                blocks: vec!["1 < 2\n3\n".into(), "1 2\n3 4\n".into()],
+                answers: vec![(1, 1, "11".into()), (2, 1, "31".into())],
+            }
+        );
+    }
+
+    #[test]
+    fn skips_parts_without_examples() {
+        let html = "<article><p>Answer <code><em>3</em></code></p></article>";
+        assert_eq!(PageExamples::from_html(html).answers, vec![]);
+    }
+
+    #[test]
+    fn numbers_parts_by_their_article() {
+        let html = r#"<article><p>No answer for part one.</p></article>
+            <article><pre><code>1 2</code></pre><p>Answer <code><em>3</em></code></p></article>"#;
+
+        assert_eq!(
+            PageExamples::from_html(html).answers,
+            vec![(2, 0, "3".into())]
+        );
+    }
+}
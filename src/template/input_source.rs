@@ -0,0 +1,181 @@
+/// Where a solution reads its input from.
+///
+/// Solution binaries read the puzzle input in `data/inputs/DD.txt` by default. `--input <path>` reads another
+/// file instead, `--input -` reads stdin and `--example <n>` reads the example in `data/examples/DD-N.txt`.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::template::PuzzleId;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The personal puzzle input of a day.
+    #[default]
+    Puzzle,
+    File(PathBuf),
+    Stdin,
+    /// The numbered example of a day.
+    Example(u8),
+}
+
+impl InputSource {
+    /// Creates a source from the values of `--input` and `--example`, which exclude each other.
+    pub fn from_options(input: Option<String>, example: Option<u8>) -> Result<Self, String> {
+        match (input, example) {
+            (Some(_), Some(_)) => Err("`--input` and `--example` cannot be combined.".into()),
+            (Some(input), None) if input == "-" => Ok(InputSource::Stdin),
+            (Some(input), None) => Ok(InputSource::File(input.into())),
+            (None, Some(example)) => Ok(InputSource::Example(example)),
+            (None, None) => Ok(InputSource::Puzzle),
+        }
+    }
+
+    /// Parse the source passed to a solution binary as `--input <path>` or `--example <n>`.
+    pub fn from_args() -> Result<Self, String> {
+        let args: Vec<String> = env::args().collect();
+        let value = |name: &str| {
+            args.iter()
+                .position(|x| x == name)
+                .map(|i| {
+                    args.get(i + 1)
+                        .cloned()
+                        .ok_or(format!("`{name}` expects a value."))
+                })
+                .transpose()
+        };
+
+        let example = value("--example")?
+            .map(|n| {
+                n.parse::<u8>()
+                    .or(Err(format!("invalid example number `{n}`.")))
+            })
+            .transpose()?;
+
+        InputSource::from_options(value("--input")?, example)
+    }
+
+    /// The arguments that select this source in a child invocation of a solution binary.
+    pub fn args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::File(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+            InputSource::Example(n) => vec!["--example".into(), n.to_string()],
+        }
+    }
+
+    /// Whether answers for this source can be submitted.
+    pub fn is_puzzle_input(&self) -> bool {
+        matches!(self, InputSource::Puzzle)
+    }
+
+    /// The path of the file this source reads for a puzzle, if it reads a file.
+    pub fn path(&self, puzzle: PuzzleId) -> Option<PathBuf> {
+        match self {
+            InputSource::Puzzle => Some(puzzle.data_path("inputs", ".txt")),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+            InputSource::Example(n) => Some(puzzle.data_path("examples", &format!("-{n}.txt"))),
+        }
+    }
+
+    /// Reads the input of a puzzle from this source.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, String> {
+        let error =
+            |e: io::Error| format!("could not read input from {}: {e}", self.describe(puzzle));
+
+        match self.path(puzzle) {
+            Some(path) => fs::read_to_string(path).map_err(error),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map_err(error)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Describes where the input of a puzzle is read from, e.g. `data/examples/01-2.txt` or `stdin`.
+    pub fn describe(&self, puzzle: PuzzleId) -> String {
+        self.path(puzzle)
+            .map_or_else(|| "stdin".into(), |path| path.display().to_string())
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "puzzle input"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Example(n) => write!(f, "example {n}"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::day;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_options() {
+        assert_eq!(
+            InputSource::from_options(None, None),
+            Ok(InputSource::Puzzle)
+        );
+        assert_eq!(
+            InputSource::from_options(Some("-".into()), None),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            InputSource::from_options(Some("edge.txt".into()), None),
+            Ok(InputSource::File("edge.txt".into()))
+        );
+        assert_eq!(
+            InputSource::from_options(None, Some(2)),
+            Ok(InputSource::Example(2))
+        );
+        assert!(InputSource::from_options(Some("-".into()), Some(2)).is_err());
+    }
+
+    #[test]
+    fn passes_source_to_children() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Stdin,
+            InputSource::File("edge.txt".into()),
+            InputSource::Example(2),
+        ] {
+            let args = source.args();
+            let value = |name: &str| {
+                let i = args.iter().position(|x| x == name)?;
+                args.get(i + 1).cloned()
+            };
+            let example = value("--example").map(|n| n.parse().unwrap());
+            assert_eq!(
+                InputSource::from_options(value("--input"), example),
+                Ok(source)
+            );
+        }
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let puzzle = day!(1).into();
+        assert_eq!(
+            InputSource::Puzzle.path(puzzle),
+            Some(PathBuf::from("data/inputs/01.txt"))
+        );
+        assert_eq!(
+            InputSource::Example(2).path(puzzle),
+            Some(PathBuf::from("data/examples/01-2.txt"))
+        );
+        assert_eq!(InputSource::Stdin.describe(puzzle), "stdin");
+    }
+}
@@ -0,0 +1,216 @@
+/// Per-day limits for running a solution, configured in `data/limits.json`:
+///
+/// ```json
+/// {
+///   "default": { "timeout_ms": 10000 },
+///   "data": [{ "day": "17", "timeout_ms": 60000, "memory_mb": 512 }]
+/// }
+/// ```
+///
+/// The limits of a day fall back to the `default` entry for every limit they do not set.
+use std::{collections::HashMap, fs, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, data_dir};
+
+static LIMITS_FILE_NAME: &str = "limits.json";
+
+/// Limits for a single run of a part. A part that exceeds them is abandoned and reported with a failure status.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Wall-clock time a part may take.
+    pub timeout: Option<Duration>,
+    /// Resident memory a part may add to the process while it runs, in bytes. Measured for the whole process,
+    /// so memory that other threads allocate in the meantime counts as well.
+    pub memory: Option<u64>,
+}
+
+/// The limits of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayLimits {
+    pub day: Day,
+    pub limits: Limits,
+}
+
+/// Limits of a year, see the module documentation.
+/// Can be deserialized from JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LimitsConfig {
+    pub default: Limits,
+    pub data: Vec<DayLimits>,
+}
+
+impl Limits {
+    /// The limits of a puzzle, read from the limits file of its year. Without a file, parts are not limited.
+    pub fn for_puzzle(puzzle: PuzzleId) -> Self {
+        LimitsConfig::read_from_file(puzzle).for_day(puzzle.day)
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.memory.is_none()
+    }
+
+    fn or(self, other: Limits) -> Self {
+        Limits {
+            timeout: self.timeout.or(other.timeout),
+            memory: self.memory.or(other.memory),
+        }
+    }
+}
+
+impl LimitsConfig {
+    /// Rehydrate limits from the JSON file of a puzzle's year. If not present, returns no limits.
+    pub fn read_from_file(puzzle: PuzzleId) -> Self {
+        let Ok(s) = fs::read_to_string(data_dir(puzzle.year).join(LIMITS_FILE_NAME)) else {
+            return LimitsConfig::default();
+        };
+
+        match LimitsConfig::try_from(s) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("{e}");
+                LimitsConfig::default()
+            }
+        }
+    }
+
+    pub fn for_day(&self, day: Day) -> Limits {
+        self.data
+            .iter()
+            .find(|d| d.day == day)
+            .map_or(self.default, |d| d.limits.or(self.default))
+    }
+}
+
+/// The resident memory of the current process in bytes. Only available on Linux.
+pub fn resident_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|kb| kb.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for LimitsConfig {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let default = match json.get("default") {
+            Some(v) => Limits::try_from(v)?,
+            None => Limits::default(),
+        };
+
+        let data = match json.get("data") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.data` to be an array.")?
+                .iter()
+                .map(DayLimits::try_from)
+                .collect::<Result<_, _>>()?,
+            None => vec![],
+        };
+
+        Ok(LimitsConfig { default, data })
+    }
+}
+
+impl TryFrom<&JsonValue> for DayLimits {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let day = value
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|json| json.get("day"))
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected limits.day to be a Day struct.")?;
+
+        Ok(DayLimits {
+            day,
+            limits: Limits::try_from(value)?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Limits {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected limits to be a JSON object.")?;
+
+        let number = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) => v
+                .get::<f64>()
+                .filter(|n| **n >= 0.0)
+                .map(|n| Some(*n as u64))
+                .ok_or(format!("Expected limits.{key} to be a positive number.")),
+        };
+
+        Ok(Limits {
+            timeout: number("timeout_ms")?.map(Duration::from_millis),
+            memory: number("memory_mb")?.map(|mb| mb * 1024 * 1024),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Limits, LimitsConfig};
+    use crate::day;
+    use std::time::Duration;
+
+    #[test]
+    fn falls_back_to_default_limits() {
+        let config = LimitsConfig::try_from(
+            r#"{
+                "default": { "timeout_ms": 10000, "memory_mb": 256 },
+                "data": [{ "day": "17", "timeout_ms": 60000 }]
+            }"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.for_day(day!(17)),
+            Limits {
+                timeout: Some(Duration::from_secs(60)),
+                memory: Some(256 * 1024 * 1024),
+            }
+        );
+        assert_eq!(
+            config.for_day(day!(1)),
+            Limits {
+                timeout: Some(Duration::from_secs(10)),
+                memory: Some(256 * 1024 * 1024),
+            }
+        );
+    }
+
+    #[test]
+    fn does_not_limit_by_default() {
+        let config = LimitsConfig::try_from(r#"{ "data": [] }"#.to_string()).unwrap();
+        assert!(config.for_day(day!(1)).is_unlimited());
+    }
+
+    #[test]
+    fn rejects_malformed_limits() {
+        let config = LimitsConfig::try_from(r#"{ "default": { "timeout_ms": "1s" } }"#.to_string());
+        assert!(config.is_err());
+    }
+}
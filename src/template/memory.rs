@@ -0,0 +1,151 @@
+/// Heap usage of the runs of a solution. Usage is only recorded when the solutions are built with the
+/// `count-allocs` or the `dhat-heap` feature, see [`HeapProfiling`].
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use tinyjson::JsonValue;
+
+/// Units of [`format_bytes`], in steps of 1024.
+const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+/// How the heap usage of runs is measured, selected with `--count-allocs` or `--dhat`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeapProfiling {
+    #[default]
+    Off,
+    /// Count allocations with the global allocator of the `count-allocs` feature. Cheap enough to bench with.
+    Count,
+    /// Profile with dhat, which needs the `dhat` build profile and only measures the first run of a part.
+    Dhat,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Most bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+    /// Number of allocations over the run.
+    pub allocations: u64,
+    /// Bytes allocated over the run, including those freed again.
+    pub total_bytes: u64,
+}
+
+impl MemoryStats {
+    /// Heap usage since the dhat profiler of the current run was started.
+    #[cfg(feature = "dhat-heap")]
+    pub fn from_profiler() -> Self {
+        let stats = dhat::HeapStats::get();
+
+        MemoryStats {
+            peak_bytes: stats.max_bytes as u64,
+            allocations: stats.total_blocks,
+            total_bytes: stats.total_bytes,
+        }
+    }
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocations, {} allocated",
+            format_bytes(self.peak_bytes),
+            self.allocations,
+            format_bytes(self.total_bytes)
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `512 B` or `1.2 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")? as u64,
+            allocations: number("allocations")? as u64,
+            total_bytes: number("total_bytes")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MemoryStats, format_bytes};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(1_258_291), "1.2 MiB");
+        assert_eq!(format_bytes(5 << 40), "5120.0 GiB");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let memory = MemoryStats {
+            peak_bytes: 2048,
+            allocations: 340,
+            total_bytes: 1 << 20,
+        };
+
+        assert_eq!(MemoryStats::try_from(&JsonValue::from(&memory)), Ok(memory));
+        assert_eq!(
+            memory.to_string(),
+            "peak 2.0 KiB, 340 allocations, 1.0 MiB allocated"
+        );
+    }
+}
@@ -1,15 +1,40 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod registry;
 pub mod runner;
+pub mod variants;
 
 pub use day::*;
+pub use input_source::InputSource;
+pub use memory::HeapProfiling;
+pub use puzzle_id::*;
+pub use readme_benchmarks::{Column, Columns};
+pub use report::{OutputFormat, PartReport, Phase, Status};
+pub use run_multi::Execution;
+pub use year::*;
 
+mod answers;
+mod bench_history;
+mod benchmark_chart;
+// dhat installs a global allocator of its own, and profiles the same numbers.
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+mod counting_alloc;
 mod day;
+mod input_source;
+mod limits;
+mod memory;
+mod puzzle_id;
+mod puzzle_text;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
 mod run_multi;
+mod stats;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,52 +42,168 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, ".txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<PuzzleId>, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, &format!("-{part}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The day can be prefixed with the year of the event, e.g. `solution!(2024, 17)`,
+/// which keeps the data of the puzzle in `data/2024/`.
+///
+/// Parts return an [`Option`], or a [`Result`] whose error is shown as the cause of a missing answer,
+/// see [`PartResult`](runner::PartResult).
+///
+/// The optional `parse = <fn>` parameter splits the solution into two phases: the function turns the
+/// input into a shared value, which `part_one` and `part_two` receive by reference. Parsing is timed separately.
+///
+/// The optional `part = 1` or `part = 2` parameter allows you to only run a single part of the solution.
+/// It goes last, e.g. `solution!(17, parse = parse, part = 1)`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:literal, $day:literal $(, $($options:tt)*)?) => {
+        $crate::solution!(@options Some($crate::year!($year)), $day, [$($($options)*)?]);
+    };
+    ($day:literal $(, $($options:tt)*)?) => {
+        $crate::solution!(@options None, $day, [$($($options)*)?]);
+    };
+
+    (@options $year:expr, $day:expr, []) => {
+        $crate::solution!(@impl $year, $day, [], [part_one, 1] [part_two, 2]);
+    };
+    (@options $year:expr, $day:expr, [part = 1]) => {
+        $crate::solution!(@impl $year, $day, [], [part_one, 1]);
+    };
+    (@options $year:expr, $day:expr, [part = 2]) => {
+        $crate::solution!(@impl $year, $day, [], [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@options $year:expr, $day:expr, [parse = $parse:path]) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@options $year:expr, $day:expr, [parse = $parse:path, part = 1]) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_one, 1]);
+    };
+    (@options $year:expr, $day:expr, [parse = $parse:path, part = 2]) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl $year:expr, $day:expr, [$($parse:path)?], $( [$func:expr, $part:expr] )*) => {
+        /// The current puzzle.
+        const DAY: $crate::template::PuzzleId =
+            $crate::template::PuzzleId::new($year, $crate::day!($day));
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// Registers the solution in the `solutions` binary, which runs all days in one process.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle: DAY,
+            run: |input, bench_time| {
+                use $crate::template::runner::*;
+                let mut reports = vec![];
+                $(
+                    let (input, report) = time_parse($parse, input, DAY, bench_time);
+                    reports.push(report);
+                    let Some(input) = input else {
+                        return reports;
+                    };
+                )?
+                $( reports.push(time_part($func, input, DAY, $part, bench_time)); )*
+                reports
+            },
+        };
+
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            // parts run on threads of their own, which need the input for the lifetime of the process.
+            let input: &'static str = read_input(DAY).leak();
+            $( let input = run_parse($parse, input, DAY); )?
+            $( run_part($func, input, DAY, $part); )*
+        }
+    };
+}
+
+/// Generates tests that check the parts of a solution against the answers of its examples, which are declared
+/// in `data/examples/DD.json`. See [`examples`] for the format.
+///
+/// Takes the same `parse = <fn>` and `part = 1` or `part = 2` options as [`solution`](crate::solution),
+/// e.g. `example_tests!(parse = parse)`.
+#[macro_export]
+macro_rules! example_tests {
+    (@options []) => {
+        $crate::example_tests!(@impl [], [test_examples_part_one, part_one, 1] [test_examples_part_two, part_two, 2]);
+    };
+    (@options [part = 1]) => {
+        $crate::example_tests!(@impl [], [test_examples_part_one, part_one, 1]);
+    };
+    (@options [part = 2]) => {
+        $crate::example_tests!(@impl [], [test_examples_part_two, part_two, 2]);
+    };
+    (@options [parse = $parse:path]) => {
+        $crate::example_tests!(@impl [$parse], [test_examples_part_one, part_one, 1] [test_examples_part_two, part_two, 2]);
+    };
+    (@options [parse = $parse:path, part = 1]) => {
+        $crate::example_tests!(@impl [$parse], [test_examples_part_one, part_one, 1]);
+    };
+    (@options [parse = $parse:path, part = 2]) => {
+        $crate::example_tests!(@impl [$parse], [test_examples_part_two, part_two, 2]);
+    };
+    (@impl $parse:tt, $( [$test:ident, $func:ident, $part:expr] )*) => {
+        $(
+            #[test]
+            fn $test() {
+                $crate::template::examples::check_examples(DAY, $part, |input| {
+                    $func($crate::example_tests!(@input $parse, input))
+                });
+            }
+        )*
+    };
+    (@input [$parse:path], $input:ident) => {
+        &$parse($input)
+    };
+    (@input [], $input:ident) => {
+        $input
+    };
+    ($($options:tt)*) => {
+        $crate::example_tests!(@options [$($options)*]);
+    };
+}
+
+/// Generates an ignored test that compares alternative implementations of the parts of a solution, run with
+/// `cargo compare <day>`. Each part lists its variants by name, see [`variants`] for details, e.g.
+/// `compare_variants! { 1 => ["part_one" => part_one, "bounded" => |input| _part_one(input, 101)] }`.
+#[macro_export]
+macro_rules! compare_variants {
+    ($( $part:literal => [ $( $name:literal => $func:expr ),+ $(,)? ] ),+ $(,)?) => {
+        #[test]
+        #[ignore = "benchmark, run with `cargo compare <day>`"]
+        fn compare_variants() {
+            use $crate::template::variants::{compare_variants, variant};
+
+            // compare every part before failing, so that one disagreement does not hide the others.
+            let failures: Vec<String> = [
+                $( compare_variants(DAY, $part, vec![$( variant($name, $func) ),+]).err(), )+
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+
+            assert!(failures.is_empty(), "{}", failures.join("\n"));
         }
     };
 }
@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::{Day, Year};
+
+/// Identifies a puzzle by its day and, optionally, the year of the event.
+///
+/// Puzzles without a year use the flat repository layout (`data/inputs/01.txt`, `src/bin/01.rs`),
+/// puzzles with a year are kept side by side (`data/2024/inputs/01.txt`, `src/bin/2024-01.rs`).
+///
+/// # Display
+/// This value displays as the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2024), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Option<Year>,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Option<Year>, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Returns the year of this puzzle, falling back to the `AOC_YEAR` environment variable.
+    pub fn year_or_env(&self) -> Option<Year> {
+        self.year.or_else(Year::from_env)
+    }
+
+    /// Returns the path to a file of this puzzle in a data folder, e.g. `data/2024/inputs/08.txt`.
+    pub fn data_path(&self, folder: &str, suffix: &str) -> PathBuf {
+        data_dir(self.year)
+            .join(folder)
+            .join(format!("{}{suffix}", self.day))
+    }
+
+    /// Returns the path to the solution binary of this puzzle.
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{self}.rs")
+    }
+}
+
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self::new(None, day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year}-{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/// Returns the data directory for a year, or the top-level data directory if there is none.
+pub fn data_dir(year: Option<Year>) -> PathBuf {
+    let data = PathBuf::from("data");
+    match year {
+        Some(year) => data.join(year.to_string()),
+        None => data,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = match s.split_once('-') {
+            Some((year, day)) => (Some(year.parse().map_err(|_| PuzzleIdFromStrError)?), day),
+            None => (None, s),
+        };
+
+        let day = day.parse().map_err(|_| PuzzleIdFromStrError)?;
+        Ok(Self::new(year, day))
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a day number like `08` or a year and day like `2024-08`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_and_displays_ids() {
+        let flat: PuzzleId = "08".parse().unwrap();
+        assert_eq!(flat, PuzzleId::new(None, day!(8)));
+        assert_eq!(flat.to_string(), "08");
+
+        let yearly: PuzzleId = "2023-17".parse().unwrap();
+        assert_eq!(yearly, PuzzleId::new(Some(year!(2023)), day!(17)));
+        assert_eq!(yearly.to_string(), "2023-17");
+
+        assert!("2023-26".parse::<PuzzleId>().is_err());
+        assert!("1999-01".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn builds_year_aware_paths() {
+        let flat = PuzzleId::new(None, day!(8));
+        assert_eq!(
+            flat.data_path("inputs", ".txt"),
+            PathBuf::from("data/inputs/08.txt")
+        );
+        assert_eq!(flat.bin_path(), "./src/bin/08.rs");
+
+        let yearly = PuzzleId::new(Some(year!(2023)), day!(8));
+        assert_eq!(
+            yearly.data_path("examples", "-2.txt"),
+            PathBuf::from("data/2023/examples/08-2.txt")
+        );
+        assert_eq!(yearly.bin_path(), "./src/bin/2023-08.rs");
+    }
+}
@@ -0,0 +1,369 @@
+/// Renders pages of the puzzle server to Markdown for `data/puzzles/DD.md`, or to ANSI for the terminal.
+///
+/// Only the `<article>` elements of a page are rendered, with the `Your puzzle answer was` paragraphs that
+/// follow solved parts. Supported are headings, paragraphs, code blocks, inline code, emphasis, links and
+/// lists; other tags are dropped and their text is kept.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+const ANSI_CODE: &str = "\x1b[36m";
+
+/// How a page is rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flavor {
+    Markdown,
+    Ansi,
+}
+
+/// Renders the puzzle description of a page. Pages without articles, e.g. error pages, are rendered whole.
+pub fn render(html: &str, flavor: Flavor) -> String {
+    let sections = sections(html);
+    let sections = if sections.is_empty() {
+        vec![html]
+    } else {
+        sections
+    };
+
+    let mut renderer = Renderer::new(flavor);
+    for section in sections {
+        // an answer is highlighted like the emphasis around it, move the emphasis outside of the code.
+        let section = section
+            .replace("<code><em>", "<em><code>")
+            .replace("</em></code>", "</code></em>");
+        renderer.render(&section);
+        renderer.end_block();
+    }
+
+    renderer.finish()
+}
+
+/// Whether the description of part two is on a page, i.e. part one has been solved.
+pub fn has_part_two(html: &str) -> bool {
+    html.matches("<article").count() >= 2
+}
+
+/// The articles of a page and the answer paragraphs between them, in order.
+fn sections(html: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut offset = 0;
+
+    loop {
+        let article = html[offset..].find("<article").map(|i| (i, "</article>"));
+        let answer = html[offset..]
+            .find("<p>Your puzzle answer was")
+            .map(|i| (i, "</p>"));
+
+        let Some((start, close)) = [article, answer].into_iter().flatten().min() else {
+            break;
+        };
+
+        let start = offset + start;
+        let Some(end) = html[start..].find(close).map(|i| start + i + close.len()) else {
+            break;
+        };
+
+        sections.push(&html[start..end]);
+        offset = end;
+    }
+
+    sections
+}
+
+/// A tag or the text between tags.
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+
+        let Some(end) = rest[start..].find('>').map(|i| start + i) else {
+            tokens.push(Token::Text(&rest[start..]));
+            break;
+        };
+
+        let tag = rest[start + 1..end].trim_end_matches('/');
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim()));
+        } else if !tag.starts_with('!') {
+            let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tokens.push(Token::Open(name, attributes));
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    tokens
+}
+
+/// The value of an attribute of an opening tag, e.g. `href` of `<a href="/2024">`.
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let (_, rest) = attributes.split_once(&format!("{name}=\""))?;
+    rest.split_once('"').map(|(value, _)| value)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+struct Renderer {
+    flavor: Flavor,
+    out: String,
+    /// Active ANSI styles, re-applied when an inner style ends.
+    styles: Vec<&'static str>,
+    /// Targets of the open links.
+    links: Vec<String>,
+    in_pre: bool,
+    /// Number of open elements whose whitespace is significant, e.g. paragraphs.
+    inline_depth: usize,
+}
+
+impl Renderer {
+    fn new(flavor: Flavor) -> Self {
+        Self {
+            flavor,
+            out: String::new(),
+            styles: vec![],
+            links: vec![],
+            in_pre: false,
+            inline_depth: 0,
+        }
+    }
+
+    fn render(&mut self, html: &str) {
+        for token in tokenize(html) {
+            match token {
+                Token::Open(name, attributes) => self.open(name, attributes),
+                Token::Close(name) => self.close(name),
+                Token::Text(text) => self.text(text),
+            }
+        }
+    }
+
+    fn open(&mut self, name: &str, attributes: &str) {
+        match (name, self.flavor) {
+            ("h2", Flavor::Markdown) => {
+                self.start_block();
+                self.out.push_str("## ");
+                self.inline_depth += 1;
+            }
+            ("h2", Flavor::Ansi) => {
+                self.start_block();
+                self.push_style(ANSI_BOLD);
+                self.inline_depth += 1;
+            }
+            ("p", _) => {
+                self.start_block();
+                self.inline_depth += 1;
+            }
+            ("li", _) => {
+                self.start_line();
+                self.out.push_str(match self.flavor {
+                    Flavor::Markdown => "- ",
+                    Flavor::Ansi => "  • ",
+                });
+                self.inline_depth += 1;
+            }
+            ("ul", _) => self.start_block(),
+            ("pre", _) => {
+                self.start_block();
+                self.in_pre = true;
+                match self.flavor {
+                    Flavor::Markdown => self.out.push_str("```\n"),
+                    Flavor::Ansi => {
+                        self.out.push_str("    ");
+                        self.push_style(ANSI_CODE);
+                    }
+                }
+            }
+            ("code", Flavor::Markdown) if !self.in_pre => self.out.push('`'),
+            ("code", Flavor::Ansi) if !self.in_pre => self.push_style(ANSI_CODE),
+            ("em", Flavor::Markdown) if !self.in_pre => self.out.push('*'),
+            ("em", Flavor::Ansi) => self.push_style(ANSI_BOLD),
+            ("a", Flavor::Markdown) => {
+                self.out.push('[');
+                self.links
+                    .push(attribute(attributes, "href").unwrap_or_default().into());
+            }
+            ("a", Flavor::Ansi) => self.push_style(ANSI_UNDERLINE),
+            ("br", _) => self.out.push('\n'),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match (name, self.flavor) {
+            ("h2" | "p" | "li", _) => {
+                if name == "h2" && self.flavor == Flavor::Ansi {
+                    self.pop_style();
+                }
+                self.inline_depth = self.inline_depth.saturating_sub(1);
+                self.end_line();
+            }
+            ("ul", _) => self.end_block(),
+            ("pre", _) => {
+                self.in_pre = false;
+                match self.flavor {
+                    Flavor::Markdown => {
+                        self.end_line();
+                        self.out.push_str("```");
+                    }
+                    Flavor::Ansi => {
+                        // the last line break of a block is followed by the indentation of a next line.
+                        if self.out.ends_with("\n    ") {
+                            self.out.truncate(self.out.len() - 4);
+                        }
+                        self.pop_style();
+                    }
+                }
+                self.end_block();
+            }
+            ("code", Flavor::Markdown) if !self.in_pre => self.out.push('`'),
+            ("code", Flavor::Ansi) if !self.in_pre => self.pop_style(),
+            ("em", Flavor::Markdown) if !self.in_pre => self.out.push('*'),
+            ("em", Flavor::Ansi) => self.pop_style(),
+            ("a", Flavor::Markdown) => {
+                let href = self.links.pop().unwrap_or_default();
+                self.out.push_str(&format!("]({href})"));
+            }
+            ("a", Flavor::Ansi) => self.pop_style(),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+
+        if self.in_pre {
+            match self.flavor {
+                Flavor::Markdown => self.out.push_str(&text),
+                Flavor::Ansi => self.out.push_str(&text.replace('\n', "\n    ")),
+            }
+        } else if self.inline_depth > 0 {
+            self.out.push_str(&text.replace('\n', " "));
+        } else if !text.trim().is_empty() {
+            self.out.push_str(text.trim());
+        }
+    }
+
+    fn push_style(&mut self, style: &'static str) {
+        self.styles.push(style);
+        self.out.push_str(style);
+    }
+
+    fn pop_style(&mut self) {
+        self.styles.pop();
+        self.out.push_str(ANSI_RESET);
+        for style in &self.styles {
+            self.out.push_str(style);
+        }
+    }
+
+    fn start_line(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn end_line(&mut self) {
+        self.start_line();
+    }
+
+    /// Separates a block from the previous one with an empty line.
+    fn start_block(&mut self) {
+        self.start_line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn end_block(&mut self) {
+        self.start_line();
+    }
+
+    fn finish(self) -> String {
+        self.out.trim().to_string()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Flavor, has_part_two, render};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>A <em>list</em> &lt;3, see <a href="/2024/about">about</a>.</p>
+<pre><code>3   4
+4   <em>3</em>
+</code></pre>
+<ul>
+<li>first</li>
+<li>second <code>x</code></li>
+</ul>
+<p>The total is <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<p>Answer: <input type="text" name="answer"/></p>
+</main></body></html>"#;
+
+    #[test]
+    fn renders_markdown() {
+        assert_eq!(
+            render(PAGE, Flavor::Markdown),
+            "## --- Day 1: Historian Hysteria ---\n\n\
+            A *list* <3, see [about](/2024/about).\n\n\
+            ```\n3   4\n4   3\n```\n\n\
+            - first\n\
+            - second `x`\n\n\
+            The total is *`11`*.\n\n\
+            Your puzzle answer was `1234`."
+        );
+    }
+
+    #[test]
+    fn renders_ansi() {
+        let text = render(PAGE, Flavor::Ansi);
+        assert!(text.starts_with(&format!(
+            "{ANSI_BOLD}--- Day 1: Historian Hysteria ---{ANSI_RESET}\n\nA {ANSI_BOLD}list{ANSI_RESET} <3"
+        )));
+        assert!(text.contains("    \x1b[36m3   4\n    4   \x1b[1m3\x1b[0m\x1b[36m\n\x1b[0m"));
+        assert!(text.contains("  • first\n"));
+        assert!(!text.contains("Answer:"));
+    }
+
+    #[test]
+    fn renders_pages_without_articles() {
+        assert_eq!(
+            render("<p>That's the <em>right</em> answer!</p>", Flavor::Markdown),
+            "That's the *right* answer!"
+        );
+    }
+
+    #[test]
+    fn detects_part_two() {
+        assert!(!has_part_two(PAGE));
+        assert!(has_part_two(
+            "<article><p>1</p></article><article><p>2</p></article>"
+        ));
+    }
+}
@@ -1,12 +1,55 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{error, fmt::Display, fs, io, str::FromStr};
 
+use crate::template::benchmark_chart;
+use crate::template::memory::format_bytes;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Width of the inline bar of the slowest day, in characters.
+const BAR_WIDTH: usize = 20;
+/// Partial blocks of an inline bar, in eighths of a character.
+const BAR_EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// An optional column of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Time spent parsing the input.
+    Parse,
+    /// Number of samples of each part.
+    Samples,
+    /// Standard deviation of each part.
+    StdDev,
+    /// Peak heap usage of each part, if it was timed with `--count-allocs` or `--dhat`.
+    Memory,
+    /// Share of the day in the total runtime.
+    Share,
+    /// Inline bar of the day's runtime, relative to the slowest day.
+    Bar,
+    /// Link to the SVG chart of all days, see [`benchmark_chart`].
+    Chart,
+}
+
+/// The optional columns of the benchmark table, selected with `time --store --columns <columns>`,
+/// e.g. `--columns parse,samples,std-dev,memory,share,bar,chart`. Only the parse column is shown by default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Columns(Vec<Column>);
+
+impl Columns {
+    pub fn contains(&self, column: Column) -> bool {
+        self.0.contains(&column)
+    }
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Columns(vec![Column::Parse])
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -21,22 +64,18 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates a table that is enclosed by two markers, including the markers.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
@@ -52,76 +91,286 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Locates the table of a year, including its markers, and returns the marker it is enclosed by.
+///
+/// Tables are enclosed by markers of their year, e.g. `<!--- benchmarking table 2023 --->`, so that the tables
+/// of several years can be kept in one README. The table of the default year may be enclosed by the marker
+/// without a year instead. A year without a table gets a marker after the last table of the other years.
+pub fn locate_year_table(
+    readme: &mut String,
+    marker: &str,
+    year: Option<Year>,
+) -> Result<(TablePosition, String), Error> {
+    let default_year = Year::from_env();
+    let Some(year) = year.or(default_year) else {
+        return Ok((locate_table(readme, marker)?, marker.into()));
+    };
+
+    let prefix = marker.trim_end_matches("--->");
+    let year_marker = format!("{prefix}{year} --->");
+
+    if !readme.contains(&year_marker) {
+        if Some(year) == default_year && readme.contains(marker) {
+            return Ok((locate_table(readme, marker)?, marker.into()));
+        }
+
+        let end = readme
+            .rfind(prefix)
+            .and_then(|start| Some(start + readme[start..].find("--->")? + "--->".len()))
+            .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        readme.insert_str(end, &format!("\n\n{year_marker}"));
+    }
+
+    Ok((locate_table(readme, &year_marker)?, year_marker))
+}
+
+/// Formats a value of both parts of a day, e.g. `` `10` / `20` ``.
+fn per_part<T>(part_1: &Option<T>, part_2: &Option<T>, value: impl Fn(&T) -> String) -> String {
+    let format = |part: &Option<T>| {
+        part.as_ref()
+            .map_or_else(|| "-".into(), |part| format!("`{}`", value(part)))
+    };
+
+    format!("{} / {}", format(part_1), format(part_2))
+}
+
+/// Draws a bar of `fraction` times [`BAR_WIDTH`] characters, with a precision of an eighth character.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn bar(fraction: f64) -> String {
+    let eighths = (fraction * (BAR_WIDTH * 8) as f64).round() as usize;
+    let bar = "█".repeat(eighths / 8) + BAR_EIGHTHS[eighths % 8];
+
+    if bar.is_empty() && fraction > 0.0 {
+        BAR_EIGHTHS[1].into()
+    } else {
+        bar
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    timings: Timings,
+    total_millis: f64,
+    year: Option<Year>,
+    columns: &Columns,
+) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Benchmarks"),
+        None => format!("{prefix} Benchmarks"),
+    };
+
+    let optional_headers = [
+        (Column::Samples, "Samples"),
+        (Column::StdDev, "Std Dev"),
+        (Column::Memory, "Peak Memory"),
+        (Column::Share, "Share"),
+        (Column::Bar, "Relative"),
+    ];
+
+    let mut headers = vec!["Day"];
+    if columns.contains(Column::Parse) {
+        headers.push("Parse");
+    }
+    headers.extend(["Part 1", "Part 2"]);
+    headers.extend(
+        optional_headers
+            .iter()
+            .filter(|(column, _)| columns.contains(*column))
+            .map(|(_, name)| *name),
+    );
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", headers.join(" | ")),
+        format!("|{}", " :---: |".repeat(headers.len())),
     ];
 
+    let total_nanos: f64 = timings.data.iter().map(|t| t.total_nanos).sum();
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0.0, f64::max);
+
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let path = PuzzleId::new(year, timing.day).bin_path();
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+
+        if columns.contains(Column::Parse) {
+            cells.push(format!("`{}`", timing.parse.as_deref().unwrap_or("-")));
+        }
+        cells.push(format!("`{}`", timing.part_1.as_deref().unwrap_or("-")));
+        cells.push(format!("`{}`", timing.part_2.as_deref().unwrap_or("-")));
+
+        if columns.contains(Column::Samples) {
+            cells.push(per_part(
+                &timing.part_1_stats,
+                &timing.part_2_stats,
+                |stats| stats.samples.to_string(),
+            ));
+        }
+        if columns.contains(Column::StdDev) {
+            cells.push(per_part(
+                &timing.part_1_stats,
+                &timing.part_2_stats,
+                |stats| format!("±{:.1?}", stats.std_dev),
+            ));
+        }
+        if columns.contains(Column::Memory) {
+            cells.push(per_part(
+                &timing.part_1_memory,
+                &timing.part_2_memory,
+                |memory| format_bytes(memory.peak_bytes),
+            ));
+        }
+        if columns.contains(Column::Share) {
+            let share = if total_nanos > 0.0 {
+                timing.total_nanos / total_nanos * 100.0
+            } else {
+                0.0
+            };
+            cells.push(format!("{share:.1}%"));
+        }
+        if columns.contains(Column::Bar) {
+            let fraction = if max_nanos > 0.0 {
+                timing.total_nanos / max_nanos
+            } else {
+                0.0
+            };
+            cells.push(bar(fraction));
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if columns.contains(Column::Chart) {
+        lines.push(String::new());
+        lines.push(format!(
+            "[![Benchmark chart](./{0})](./{0})",
+            benchmark_chart::chart_path(year).display()
+        ));
+    }
+
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    year: Option<Year>,
+    columns: &Columns,
+) -> Result<(), Error> {
+    let (positions, marker) = locate_year_table(s, MARKER, year)?;
+    let table = construct_table("##", &marker, timings, total_millis, year, columns);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(timings: Timings, year: Option<Year>, columns: &Columns) -> Result<(), Error> {
+    if columns.contains(Column::Chart) {
+        benchmark_chart::store_file(&timings, year)?;
+    }
+
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, year, columns)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/* -------------------------------------------------------------------------- */
+
+impl Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Column::Parse => write!(f, "parse"),
+            Column::Samples => write!(f, "samples"),
+            Column::StdDev => write!(f, "std-dev"),
+            Column::Memory => write!(f, "memory"),
+            Column::Share => write!(f, "share"),
+            Column::Bar => write!(f, "bar"),
+            Column::Chart => write!(f, "chart"),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = ColumnFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "samples" => Ok(Column::Samples),
+            "std-dev" => Ok(Column::StdDev),
+            "memory" => Ok(Column::Memory),
+            "share" => Ok(Column::Share),
+            "bar" => Ok(Column::Bar),
+            "chart" => Ok(Column::Chart),
+            _ => Err(ColumnFromStrError),
+        }
+    }
+}
+
+impl FromStr for Columns {
+    type Err = ColumnFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .filter(|column| !column.trim().is_empty())
+            .map(|column| column.trim().parse())
+            .collect::<Result<_, _>>()
+            .map(Columns)
+    }
+}
+
+/// An error which can be returned when parsing a [`Column`].
+#[derive(Debug)]
+pub struct ColumnFromStrError;
+
+impl error::Error for ColumnFromStrError {}
+
+impl Display for ColumnFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a list of `parse`, `samples`, `std-dev`, `memory`, `share`, `bar` or `chart`")
+    }
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{Column, Columns, MARKER, bar, update_content};
+    use crate::template::memory::MemoryStats;
+    use crate::template::stats::BenchStats;
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
+    use std::time::Duration;
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    ..Timing::for_day(day!(1), 3e+10)
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    ..Timing::for_day(day!(2), 7e+10)
                 },
                 Timing {
-                    day: day!(4),
+                    parse: Some("5ms".into()),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    ..Timing::for_day(day!(4), 9e+10)
                 },
             ],
         }
@@ -131,28 +380,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, &Columns::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, &Columns::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, &Columns::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, &Columns::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, &Columns::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +409,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, &Columns::default()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `5ms` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +429,129 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_yearly_benchmarks() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            Some(year!(2023)),
+            &Columns::default(),
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2023 Benchmarks"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn updates_tables_of_several_years() {
+        // `AOC_YEAR` is set to 2024 in `.cargo/config.toml`, so the table without a year is the one of 2024.
+        let table_2023 =
+            "<!--- benchmarking table 2023 --->\n| old |\n<!--- benchmarking table 2023 --->";
+        let mut s = format!("{MARKER}\n| old |\n{MARKER}\n\n{table_2023}");
+
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            Some(year!(2023)),
+            &Columns::default(),
+        )
+        .unwrap();
+        assert!(s.starts_with(&format!(
+            "{MARKER}\n| old |\n{MARKER}\n\n<!--- benchmarking table 2023 --->\n## 2023 Benchmarks\n"
+        )));
+
+        update_content(&mut s, get_mock_timings(), 190.0, None, &Columns::default()).unwrap();
+        assert!(s.starts_with(&format!("{MARKER}\n## Benchmarks\n")));
+        assert!(s.contains("\n<!--- benchmarking table 2023 --->\n## 2023 Benchmarks\n"));
+        assert!(!s.contains("| old |"));
+    }
+
+    #[test]
+    fn adds_tables_of_new_years() {
+        let mut s = format!("{MARKER}\n| old |\n{MARKER}\nfoo");
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            Some(year!(2022)),
+            &Columns::default(),
+        )
+        .unwrap();
+
+        assert!(s.starts_with(&format!(
+            "{MARKER}\n| old |\n{MARKER}\n\n<!--- benchmarking table 2022 --->\n## 2022 Benchmarks\n"
+        )));
+        assert!(s.ends_with("**Total: 190.00ms**\n<!--- benchmarking table 2022 --->\nfoo"));
+    }
+
+    #[test]
+    fn parses_columns() {
+        assert_eq!(
+            "samples, std-dev,bar".parse::<Columns>().unwrap(),
+            Columns(vec![Column::Samples, Column::StdDev, Column::Bar])
+        );
+        assert!("parse,median".parse::<Columns>().is_err());
+    }
+
+    #[test]
+    fn draws_bars() {
+        assert_eq!(bar(1.0), "█".repeat(20));
+        assert_eq!(bar(0.5), "█".repeat(10));
+        assert_eq!(bar(0.0125), "▎");
+        assert_eq!(bar(0.0001), "▏");
+        assert_eq!(bar(0.0), "");
+    }
+
+    #[test]
+    fn format_optional_columns() {
+        let stats = BenchStats {
+            samples: 100,
+            outliers: 0,
+            mean: Duration::from_millis(10),
+            median: Duration::from_millis(10),
+            min: Duration::from_millis(9),
+            max: Duration::from_millis(11),
+            std_dev: Duration::from_micros(500),
+            p95: Duration::from_millis(11),
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(stats);
+        timings.data[1].part_2_memory = Some(MemoryStats {
+            peak_bytes: 1_572_864,
+            allocations: 12,
+            total_bytes: 2_097_152,
+        });
+
+        let columns = "samples,std-dev,memory,share,bar,chart"
+            .parse::<Columns>()
+            .unwrap();
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, None, &columns).unwrap();
+
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Samples | Std Dev | Peak Memory | Share | Relative |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `100` / - | `±500.0µs` / - | - / - | 15.8% | ██████▋ |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - / - | - / - | - / `1.5 MiB` | 36.8% | ███████████████▌ |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | - / - | - / - | - / - | 47.4% | ████████████████████ |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "[![Benchmark chart](./data/visualizations/benchmarks.svg)](./data/visualizations/benchmarks.svg)",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
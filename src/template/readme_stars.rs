@@ -0,0 +1,216 @@
+/// Module that updates the readme with the stars collected per day.
+/// The table has the same layout as the one of `aoc-readme-stars`, but is generated from local state:
+/// a part earns its star if it has a correct answer in the answers ledger, or if it produced an answer
+/// the last time it was timed.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_client::puzzle_url;
+use crate::template::readme_benchmarks::{Error, locate_year_table};
+use crate::template::timings::Timings;
+use crate::template::{Day, PuzzleId, Status, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+static STAR: &str = "⭐";
+
+/// The stars of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+/// Collects the stars of all days that were timed or have recorded answers, ordered by day.
+pub fn collect_stars(timings: &Timings, answers: &Answers) -> Vec<DayStars> {
+    let mut days: Vec<Day> = timings
+        .data
+        .iter()
+        .map(|t| t.day)
+        .chain(answers.data.iter().map(|a| a.day))
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let has_answer = |time: &Option<String>| {
+        time.as_deref()
+            .is_some_and(|time| Status::from_label(time).is_none())
+    };
+
+    days.into_iter()
+        .map(|day| {
+            let timing = timings.data.iter().find(|t| t.day == day);
+            let solved = |part: u8| {
+                answers.correct_answer(day, part).is_some()
+                    || timing
+                        .is_some_and(|t| has_answer(if part == 1 { &t.part_1 } else { &t.part_2 }))
+            };
+
+            DayStars {
+                day,
+                part_1: solved(1),
+                part_2: solved(2),
+            }
+        })
+        .collect()
+}
+
+fn construct_table(prefix: &str, marker: &str, stars: &[DayStars], year: Option<Year>) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Results"),
+        None => format!("{prefix} Results"),
+    };
+
+    let mut lines: Vec<String> = vec![
+        marker.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |solved: bool| if solved { STAR } else { " " };
+
+    for day in stars {
+        let puzzle = PuzzleId::new(year, day.day);
+        let link = puzzle_url(puzzle).unwrap_or_else(|| puzzle.bin_path());
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} |",
+            day.day.into_inner(),
+            link,
+            star(day.part_1),
+            star(day.part_2)
+        ));
+    }
+
+    lines.push(marker.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, stars: &[DayStars], year: Option<Year>) -> Result<(), Error> {
+    // like the links of the days, the heading falls back to the default year rather than dropping it.
+    let year = year.or_else(Year::from_env);
+    let (positions, marker) = locate_year_table(s, MARKER, year)?;
+    let table = construct_table("##", &marker, stars, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(timings: &Timings, answers: &Answers, year: Option<Year>) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, &collect_stars(timings, answers), year)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayStars, MARKER, collect_stars, update_content};
+    use crate::template::answers::{Answer, Answers};
+    use crate::template::aoc_client::SubmissionOutcome;
+    use crate::template::timings::{Timing, Timings};
+    use crate::{day, year};
+
+    fn timing(day: crate::template::Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            ..Timing::for_day(day, 0.0)
+        }
+    }
+
+    fn get_mock_stars() -> Vec<DayStars> {
+        let timings = Timings {
+            data: vec![
+                timing(day!(2), Some("30ms"), Some("TIMEOUT")),
+                timing(day!(1), Some("10ms"), Some("20ms")),
+            ],
+        };
+        let answers = Answers {
+            data: vec![Answer {
+                day: day!(3),
+                part: 1,
+                answer: "42".into(),
+                outcome: SubmissionOutcome::Correct,
+            }],
+        };
+
+        collect_stars(&timings, &answers)
+    }
+
+    #[test]
+    fn collects_stars_from_timings_and_answers() {
+        assert_eq!(
+            get_mock_stars(),
+            vec![
+                DayStars {
+                    day: day!(1),
+                    part_1: true,
+                    part_2: true
+                },
+                DayStars {
+                    day: day!(2),
+                    part_1: true,
+                    part_2: false
+                },
+                DayStars {
+                    day: day!(3),
+                    part_1: true,
+                    part_2: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_stars(), Some(year!(2024))).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\n| old |\n{MARKER}\nbaz");
+        update_content(&mut s, &get_mock_stars(), Some(year!(2024))).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn keeps_tables_of_other_years() {
+        let table_2023 = "<!--- advent_readme_stars table 2023 --->\n| old |\n<!--- advent_readme_stars table 2023 --->";
+        let mut s = format!("{MARKER}\n| old |\n{MARKER}\n\n{table_2023}");
+
+        update_content(&mut s, &get_mock_stars(), Some(year!(2023))).unwrap();
+        assert!(s.starts_with(&format!("{MARKER}\n| old |\n{MARKER}\n\n")));
+        assert!(s.contains("<!--- advent_readme_stars table 2023 --->\n## 2023 Results\n"));
+
+        update_content(&mut s, &get_mock_stars(), Some(year!(2024))).unwrap();
+        assert!(s.starts_with(&format!("{MARKER}\n## 2024 Results\n")));
+        assert!(s.contains("<!--- advent_readme_stars table 2023 --->\n## 2023 Results\n"));
+    }
+
+    #[test]
+    fn keeps_heading_of_default_year() {
+        // `AOC_YEAR` is set to 2024 in `.cargo/config.toml`, like in the heading of the README.
+        let mut s = format!("{MARKER}\n## 2024 Results\n\n| old |\n{MARKER}");
+        update_content(&mut s, &get_mock_stars(), None).unwrap();
+        assert!(s.contains(&format!("{MARKER}\n## 2024 Results\n")));
+    }
+}
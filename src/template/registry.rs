@@ -0,0 +1,122 @@
+/// The registry of the `solutions` binary, which compiles every solution into one binary so that
+/// `all` and `time` can run them in a single process. The list of solutions is generated by `build.rs`.
+use std::time::Duration;
+use std::{process, thread};
+
+use crate::template::report::{OutputFormat, PartReport};
+use crate::template::runner::{DEFAULT_BENCH_TIME, STACK_SIZE, print_report};
+use crate::template::{InputSource, PuzzleId};
+
+/// A solution compiled into the `solutions` binary. Created by the [`solution`](crate::solution) macro.
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs every phase of the solution on an input, benching each phase if a time budget is given.
+    pub run: fn(&'static str, Option<Duration>) -> Vec<PartReport>,
+}
+
+/// Entry point of the `solutions` binary.
+///
+/// Runs the puzzles passed as arguments (all registered puzzles by default) and prints their reports in the
+/// format passed as `--format`. `--time` and `--bench-time <ms>` bench the parts like for a solution binary,
+/// `--input` and `--example` select their input, see [`InputSource`]. `--parallel` runs the days on parallel
+/// threads. `--list` prints the registered puzzles instead.
+///
+/// A part that exceeds its [`Limits`](crate::template::limits::Limits) keeps running on its abandoned thread
+/// next to the other days, so `all` and `time` run days with limits in a binary of their own instead.
+pub fn run(solutions: &[Solution]) {
+    if let Err(e) = try_run(solutions) {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
+
+fn try_run(solutions: &[Solution]) -> Result<(), pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+
+    if args.contains("--list") {
+        for solution in solutions {
+            println!("{}", solution.puzzle);
+        }
+        return Ok(());
+    }
+
+    let format: OutputFormat = args.opt_value_from_str("--format")?.unwrap_or_default();
+    let parallel = args.contains("--parallel");
+    let time = args.contains("--time");
+    let bench_time: Option<u64> = args.opt_value_from_str("--bench-time")?;
+    let input = InputSource::from_options(
+        args.opt_value_from_str("--input")?,
+        args.opt_value_from_str("--example")?,
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+
+    let mut puzzles: Vec<PuzzleId> = vec![];
+    while let Some(puzzle) = args.opt_free_from_str()? {
+        puzzles.push(puzzle);
+    }
+
+    let bench_time = time.then(|| bench_time.map_or(DEFAULT_BENCH_TIME, Duration::from_millis));
+
+    let mut selected: Vec<&Solution> = solutions
+        .iter()
+        .filter(|s| puzzles.is_empty() || puzzles.contains(&s.puzzle))
+        .collect();
+
+    selected.sort_unstable_by_key(|s| s.puzzle);
+
+    let print = |reports: thread::Result<Vec<PartReport>>| {
+        // a panicking solution has already printed its message to stderr.
+        for report in reports.unwrap_or_default() {
+            print_report(&report, format);
+        }
+    };
+
+    thread::scope(|scope| {
+        if parallel {
+            let handles: Vec<_> = selected
+                .iter()
+                .map(|solution| spawn(scope, solution, bench_time, &input))
+                .collect();
+
+            // join in order, so that reports are printed day by day.
+            handles.into_iter().for_each(|h| print(h.join()));
+        } else {
+            for solution in &selected {
+                print(spawn(scope, solution, bench_time, &input).join());
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn spawn<'scope>(
+    scope: &'scope thread::Scope<'scope, '_>,
+    solution: &'scope Solution,
+    bench_time: Option<Duration>,
+    input: &'scope InputSource,
+) -> thread::ScopedJoinHandle<'scope, Vec<PartReport>> {
+    thread::Builder::new()
+        .name(solution.puzzle.to_string())
+        .stack_size(STACK_SIZE)
+        .spawn_scoped(scope, move || run_solution(solution, bench_time, input))
+        .unwrap()
+}
+
+fn run_solution(
+    solution: &Solution,
+    bench_time: Option<Duration>,
+    input: &InputSource,
+) -> Vec<PartReport> {
+    match input.read(solution.puzzle) {
+        // parts run on threads of their own, which need the input for the lifetime of the process.
+        Ok(input) => (solution.run)(input.leak(), bench_time),
+        Err(e) => {
+            eprintln!("{e}");
+            vec![]
+        }
+    }
+}
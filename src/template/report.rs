@@ -0,0 +1,464 @@
+/// Structured results of a solution run. Solution binaries emit one report per phase with `--format json`,
+/// which is the channel `run_multi` uses to collect results and timings from the child processes.
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+use crate::template::memory::MemoryStats;
+use crate::template::stats::BenchStats;
+
+/// The columns of [`PartReport::to_csv_row`].
+pub const CSV_HEADER: &str = "puzzle,phase,status,result,duration_nanos,samples,outliers,mean_nanos,std_dev_nanos,min_nanos,max_nanos,p95_nanos,peak_bytes,allocations,total_bytes,error";
+
+/// How results are printed, selected with `--format <text|json|csv>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable lines with ANSI escapes.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// One CSV row per line, see [`CSV_HEADER`].
+    Csv,
+}
+
+impl OutputFormat {
+    /// Parse the format passed to a solution binary as `--format <format>`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1)?.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `text`, `json` or `csv`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A timed phase of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(u8),
+}
+
+impl Phase {
+    /// The key of this phase in structured output, e.g. `part_1`.
+    pub fn key(&self) -> String {
+        match self {
+            Phase::Parse => "parse".into(),
+            Phase::Part(part) => format!("part_{part}"),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "parse" => Some(Phase::Parse),
+            _ => key.strip_prefix("part_")?.parse().ok().map(Phase::Part),
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "Parse"),
+            Phase::Part(part) => write!(f, "Part {part}"),
+        }
+    }
+}
+
+/// Whether a phase ran to completion, panicked, or was abandoned for exceeding its
+/// [`Limits`](crate::template::limits::Limits). A phase crashed if its process ended without a report, e.g.
+/// because the solution overflowed its stack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Status {
+    #[default]
+    Ok,
+    Panicked,
+    Timeout,
+    OutOfMemory,
+    Crashed,
+}
+
+impl Status {
+    /// The key of this status in structured output, e.g. `timeout`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Panicked => "panicked",
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "out_of_memory",
+            Status::Crashed => "crashed",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        [
+            Status::Ok,
+            Status::Panicked,
+            Status::Timeout,
+            Status::OutOfMemory,
+            Status::Crashed,
+        ]
+        .into_iter()
+        .find(|status| status.key() == key)
+    }
+
+    /// Parse the label a failed phase is shown with, e.g. in the timings of a day.
+    pub fn from_label(label: &str) -> Option<Self> {
+        [
+            Status::Panicked,
+            Status::Timeout,
+            Status::OutOfMemory,
+            Status::Crashed,
+        ]
+        .into_iter()
+        .find(|status| status.to_string() == label)
+    }
+
+    pub fn is_ok(&self) -> bool {
+        *self == Status::Ok
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Ok => write!(f, "OK"),
+            Status::Panicked => write!(f, "PANICKED"),
+            Status::Timeout => write!(f, "TIMEOUT"),
+            Status::OutOfMemory => write!(f, "OUT OF MEMORY"),
+            Status::Crashed => write!(f, "CRASHED"),
+        }
+    }
+}
+
+/// The outcome of running one phase of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub puzzle: PuzzleId,
+    pub phase: Phase,
+    pub status: Status,
+    /// The answer of a part, [`None`] for the parse phase or if the part is not solved.
+    pub result: Option<String>,
+    /// Why a phase failed, e.g. the message and location of a panic.
+    pub error: Option<String>,
+    /// Duration of the first, unbenched run, or until the phase was abandoned.
+    pub duration: Duration,
+    pub stats: Option<BenchStats>,
+    /// Heap usage of the first run, if the solution was built with a heap profiler.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartReport {
+    /// The representative duration of this phase: the bench median, or the single run if it was not benched.
+    pub fn time(&self) -> Duration {
+        self.stats.map_or(self.duration, |stats| stats.median)
+    }
+
+    pub fn samples(&self) -> u128 {
+        self.stats.map_or(1, |stats| stats.samples)
+    }
+
+    /// Parse a line of a solution's output. Returns [`None`] for lines that are not a report, e.g. debug output.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let json = JsonValue::from_str(line.trim()).ok()?;
+        PartReport::try_from(&json).ok()
+    }
+
+    pub fn to_csv_row(&self) -> String {
+        fn nanos(d: Duration) -> String {
+            d.as_nanos().to_string()
+        }
+
+        let stat = |f: fn(&BenchStats) -> String| self.stats.as_ref().map(f).unwrap_or_default();
+        let memory = |f: fn(&MemoryStats) -> u64| {
+            self.memory
+                .as_ref()
+                .map(|m| f(m).to_string())
+                .unwrap_or_default()
+        };
+
+        [
+            self.puzzle.to_string(),
+            self.phase.key(),
+            self.status.key().to_string(),
+            csv_field(self.result.as_deref().unwrap_or_default()),
+            nanos(self.time()),
+            self.samples().to_string(),
+            stat(|s| s.outliers.to_string()),
+            stat(|s| nanos(s.mean)),
+            stat(|s| nanos(s.std_dev)),
+            stat(|s| nanos(s.min)),
+            stat(|s| nanos(s.max)),
+            stat(|s| nanos(s.p95)),
+            memory(|m| m.peak_bytes),
+            memory(|m| m.allocations),
+            memory(|m| m.total_bytes),
+            csv_field(self.error.as_deref().unwrap_or_default()),
+        ]
+        .join(",")
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("puzzle".into(), JsonValue::String(value.puzzle.to_string()));
+        map.insert("phase".into(), JsonValue::String(value.phase.key()));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.key().into()),
+        );
+        map.insert(
+            "result".into(),
+            value
+                .result
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.time().as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples() as f64));
+        map.insert(
+            "first_run_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>());
+
+        let puzzle = string("puzzle")
+            .and_then(|p| p.parse().ok())
+            .ok_or("Expected report.puzzle to be a puzzle id.")?;
+
+        let phase = string("phase")
+            .and_then(|p| Phase::from_key(p))
+            .ok_or("Expected report.phase to be `parse` or `part_N`.")?;
+
+        // reports without a status are from phases that completed.
+        let status = match string("status") {
+            Some(s) => Status::from_key(s).ok_or("Expected report.status to be a status.")?,
+            None => Status::Ok,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = json
+            .get("first_run_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|nanos| Duration::from_nanos(nanos.round() as u64))
+            .ok_or("Expected report.first_run_nanos to be a number.")?;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartReport {
+            puzzle,
+            phase,
+            status,
+            result: string("result").cloned(),
+            error: string("error").cloned(),
+            duration,
+            stats,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{OutputFormat, PartReport, Phase, Status};
+    use crate::day;
+    use crate::template::PuzzleId;
+    use crate::template::memory::MemoryStats;
+    use crate::template::stats::BenchStats;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            puzzle: PuzzleId::new(None, day!(1)),
+            phase: Phase::Part(2),
+            status: Status::Ok,
+            result: Some("#..\n.#.".into()),
+            error: None,
+            duration: Duration::from_millis(3),
+            stats: BenchStats::from_samples(&[Duration::from_millis(1), Duration::from_millis(2)]),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!("csv".parse::<OutputFormat>().unwrap(), OutputFormat::Csv);
+        assert!("yaml".parse::<OutputFormat>().is_err());
+        assert_eq!(OutputFormat::Json.to_string(), "json");
+    }
+
+    #[test]
+    fn roundtrips_json_lines() {
+        let report = get_mock_report();
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartReport::from_line(&line), Some(report));
+    }
+
+    #[test]
+    fn roundtrips_memory() {
+        let mut report = get_mock_report();
+        report.memory = Some(MemoryStats {
+            peak_bytes: 1 << 20,
+            allocations: 340,
+            total_bytes: 3 << 20,
+        });
+
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(PartReport::from_line(&line), Some(report));
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(PartReport::from_line("debug output"), None);
+        assert_eq!(PartReport::from_line("[1, 2, 3]"), None);
+        assert_eq!(PartReport::from_line(r#"{ "phase": "part_1" }"#), None);
+    }
+
+    #[test]
+    fn formats_csv_rows() {
+        let mut report = get_mock_report();
+        assert_eq!(
+            report.to_csv_row(),
+            "01,part_2,ok,\"#..\n.#.\",1500000,2,0,1500000,500000,1000000,2000000,1950000,,,,"
+        );
+
+        report.stats = None;
+        report.phase = Phase::Parse;
+        report.result = None;
+        assert_eq!(report.to_csv_row(), "01,parse,ok,,3000000,1,,,,,,,,,,");
+
+        report.memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            allocations: 3,
+            total_bytes: 4096,
+        });
+        assert!(report.to_csv_row().ends_with(",2048,3,4096,"));
+    }
+
+    #[test]
+    fn roundtrips_failed_phases() {
+        let mut report = get_mock_report();
+        report.status = Status::Panicked;
+        report.result = None;
+        report.error = Some("overflow (src/bin/01.rs:2:5)".into());
+        report.stats = None;
+
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(PartReport::from_line(&line), Some(report.clone()));
+        assert!(
+            report
+                .to_csv_row()
+                .ends_with(",overflow (src/bin/01.rs:2:5)")
+        );
+        assert_eq!(Status::from_label("TIMEOUT"), Some(Status::Timeout));
+        assert_eq!(Status::from_label("1.2ms"), None);
+    }
+}
@@ -1,56 +1,313 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet, fmt::Display, io, path::Path, process::ExitStatus, time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::limits::Limits;
+use crate::template::report::{CSV_HEADER, OutputFormat, PartReport, Phase, Status};
+use crate::template::runner::print_report;
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, HeapProfiling, InputSource, PuzzleId, Year,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// How `run_multi` runs the solutions of several days.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Execution {
+    /// Every day in a binary of its own.
+    Isolated,
+    /// All days one after another in the `solutions` binary.
+    #[default]
+    InProcess,
+    /// All days on parallel threads of the `solutions` binary. Not used to bench, as the days would compete
+    /// for the CPU.
+    Parallel,
+}
 
-    let mut need_space = false;
+/// The outcome of running several days.
+pub struct RunSummary {
+    /// Timings of the days, if they were benched.
+    pub timings: Option<Timings>,
+    /// Reports of the phases that did not complete, e.g. because they panicked.
+    pub failures: Vec<PartReport>,
+}
 
+impl RunSummary {
+    pub fn has_failures(&self) -> bool {
+        !self.failures.is_empty()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    year: Option<Year>,
+    is_release: bool,
+    heap: HeapProfiling,
+    bench_time: Option<Duration>,
+    format: OutputFormat,
+    execution: Execution,
+    input: &InputSource,
+) -> RunSummary {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<PuzzleId> = all_days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
+        .map(|day| PuzzleId::new(year, day))
+        .collect();
+
+    // the `solutions` binary cannot be built with dhat, see `src/solutions.rs`.
+    let registered = match execution {
+        _ if heap == HeapProfiling::Dhat => HashSet::new(),
+        Execution::Isolated => HashSet::new(),
+        _ => child_commands::list_registered(is_release, heap).unwrap_or_else(|e| {
+            eprintln!("Could not build the solutions binary ({e}), running every day on its own.");
+            HashSet::new()
+        }),
+    };
+
+    // memory is measured for the whole process and parts over their limits keep running on abandoned threads,
+    // so days with limits run in a binary of their own rather than next to other days.
+    let registered: HashSet<PuzzleId> = registered
+        .into_iter()
+        .filter(|puzzle| Limits::for_puzzle(*puzzle).is_unlimited())
+        .collect();
+
+    let mut output = DayOutput::new(format);
+
+    if format == OutputFormat::Csv {
+        println!("{CSV_HEADER}");
+    }
+
+    let isolated = |puzzle: PuzzleId, output: &mut DayOutput| {
+        run_isolated(puzzle, is_release, heap, bench_time, input, output);
+    };
+
+    // days that cannot be compiled into the solutions binary still run as a binary of their own.
+    for chunk in puzzles.chunk_by(|a, b| registered.contains(a) == registered.contains(b)) {
+        if !registered.contains(&chunk[0]) {
+            chunk
+                .iter()
+                .for_each(|puzzle| isolated(*puzzle, &mut output));
+            continue;
+        }
+
+        let parallel = execution == Execution::Parallel;
+        let mut pending = chunk.iter().copied();
+
+        let result = child_commands::run_registry(
+            chunk,
+            bench_time,
+            is_release,
+            heap,
+            parallel,
+            input,
+            |report| {
+                // finish the days that did not report anything up to this one.
+                while output.current_day() != Some(report.puzzle.day) {
+                    let Some(puzzle) = pending.next() else { break };
+                    output.start_day(puzzle.day);
+                }
+                output.report(report);
+            },
+        );
+
+        match result {
+            Ok(()) => pending.for_each(|puzzle| output.start_day(puzzle.day)),
+            // the days of the solutions binary report all at once, so the days after the last one that
+            // reported did not finish. Rerun them on their own to find the day that ended the process.
+            Err(e) => {
+                eprintln!(
+                    "The solutions binary failed ({e}), running the remaining days on their own."
+                );
+                pending.for_each(|puzzle| isolated(puzzle, &mut output));
+            }
+        }
+    }
+
+    let (timings, failures) = output.finish();
+
+    let timings = bench_time.map(|_| Timings { data: timings });
+
+    if format == OutputFormat::Text {
+        if let Some(timings) = &timings {
+            let total_millis = timings.total_millis();
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+
+        if !failures.is_empty() {
+            if timings.is_none() {
                 println!();
             }
-            need_space = true;
+            println!(
+                "{ANSI_BOLD}Failed:{ANSI_RESET} {}",
+                describe_failures(&failures)
+            );
+        }
+    }
+
+    RunSummary { timings, failures }
+}
 
+/// Lists failed phases, e.g. `Day 01 Part 2 (PANICKED), Day 05 Parse (TIMEOUT)`.
+fn describe_failures(failures: &[PartReport]) -> String {
+    failures
+        .iter()
+        .map(|r| format!("Day {} {} ({})", r.puzzle.day, r.phase, r.status))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Runs a day in a binary of its own. A day whose binary ended without reporting why is reported as crashed.
+fn run_isolated(
+    puzzle: PuzzleId,
+    is_release: bool,
+    heap: HeapProfiling,
+    bench_time: Option<Duration>,
+    input: &InputSource,
+    output: &mut DayOutput,
+) {
+    output.start_day(puzzle.day);
+
+    // like in the solutions binary, a day without its input is not solved rather than failed.
+    if Path::new(&puzzle.bin_path()).exists()
+        && let Err(e) = input.read(puzzle)
+    {
+        eprintln!("{e}");
+        return;
+    }
+
+    let result =
+        child_commands::run_solution(puzzle, bench_time, is_release, heap, input, |report| {
+            output.report(report)
+        });
+
+    if let Err(e) = result {
+        output.crash(puzzle, &e);
+    }
+}
+
+/// Prints the reports of consecutive days and collects their timings and failures.
+struct DayOutput {
+    format: OutputFormat,
+    current: Option<(Day, Vec<PartReport>)>,
+    timings: Vec<Timing>,
+    failures: Vec<PartReport>,
+}
+
+impl DayOutput {
+    fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            current: None,
+            timings: vec![],
+            failures: vec![],
+        }
+    }
+
+    fn current_day(&self) -> Option<Day> {
+        self.current.as_ref().map(|(day, _)| *day)
+    }
+
+    /// Finishes the current day and prints the header of the next one.
+    fn start_day(&mut self, day: Day) {
+        let need_space = self.current.is_some();
+        self.finish_day();
+
+        if self.format == OutputFormat::Text {
+            if need_space {
+                println!();
+            }
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
+        }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        self.current = Some((day, vec![]));
+    }
+
+    fn report(&mut self, report: PartReport) {
+        print_report(&report, self.format);
+        if !report.status.is_ok() {
+            self.failures.push(report.clone());
+        }
+        if let Some((_, reports)) = &mut self.current {
+            reports.push(report);
+        }
+    }
+
+    /// Reports the phase after the last reported one as crashed, unless a failed phase explains why the
+    /// process of the current day ended, e.g. one that ran out of memory.
+    fn crash(&mut self, puzzle: PuzzleId, error: &Error) {
+        let reports = self
+            .current
+            .as_ref()
+            .map_or(&[][..], |(_, reports)| reports);
+        if reports.last().is_some_and(|report| !report.status.is_ok()) {
+            return;
+        }
 
-            if output.is_empty() {
+        let part = reports
+            .iter()
+            .filter_map(|report| match report.phase {
+                Phase::Part(part) => Some(part),
+                Phase::Parse => None,
+            })
+            .max()
+            .map_or(1, |part| part + 1);
+
+        self.report(PartReport {
+            puzzle,
+            phase: Phase::Part(part),
+            status: Status::Crashed,
+            result: None,
+            error: Some(error.to_string()),
+            duration: Duration::ZERO,
+            stats: None,
+            memory: None,
+        });
+    }
+
+    fn finish_day(&mut self) {
+        let Some((day, reports)) = self.current.take() else {
+            return;
+        };
+
+        if reports.is_empty() {
+            if self.format == OutputFormat::Text {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
             }
-        });
+        } else {
+            self.timings
+                .push(child_commands::timing_from_reports(&reports, day));
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+    fn finish(mut self) -> (Vec<Timing>, Vec<PartReport>) {
+        self.finish_day();
+        (self.timings, self.failures)
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    /// The child process ended without success, e.g. because a solution overflowed its stack.
+    Exit(ExitStatus),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "lost the output of the child process"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Exit(status) => write!(f, "{status}"),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -59,47 +316,164 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::report::{PartReport, Phase};
+    use crate::template::{Day, HeapProfiling, InputSource, PuzzleId};
     use std::{
+        collections::HashSet,
+        env,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Name of the binary that has all solutions compiled in, see `src/solutions.rs`.
+    const REGISTRY_BIN: &str = "solutions";
+
+    /// Variables that `cargo run` sets for this binary. Passing them on to a nested cargo invocation
+    /// would change the environment of build scripts (e.g. the one of `ring`) and trigger a rebuild.
+    const CARGO_RUN_VARIABLES: [&str; 6] = [
+        "CARGO_MANIFEST_DIR",
+        "CARGO_MANIFEST_PATH",
+        "CARGO_CRATE_NAME",
+        "CARGO_BIN_NAME",
+        "CARGO_PRIMARY_PACKAGE",
+        "OUT_DIR",
+    ];
+
+    /// Create a `cargo` command that does not inherit the environment of `cargo run`.
+    pub fn cargo_command() -> Command {
+        let mut cmd = Command::new("cargo");
+
+        for (key, _) in env::vars_os() {
+            let key = key.to_string_lossy();
+            if key.starts_with("CARGO_PKG_") || CARGO_RUN_VARIABLES.contains(&key.as_ref()) {
+                cmd.env_remove(key.as_ref());
+            }
+        }
+
+        cmd
+    }
+
+    /// Arguments of `cargo run` that select the build profile and the features of the heap profiling: the
+    /// `dhat` profile with the dhat profiler compiled in, or `--release` with the counting allocator.
+    pub fn profile_args(is_release: bool, heap: HeapProfiling) -> Vec<&'static str> {
+        let mut args = match heap {
+            HeapProfiling::Dhat => return vec!["--profile", "dhat", "--features", "dhat-heap"],
+            _ if is_release => vec!["--release"],
+            _ => vec![],
+        };
+
+        if heap == HeapProfiling::Count {
+            args.extend(["--features", "count-allocs"]);
+        }
+
+        args
+    }
+
+    /// Run the solution bin for a given day on an input, passing its reports to `on_report`.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        bench_time: Option<Duration>,
+        is_release: bool,
+        heap: HeapProfiling,
+        input: &InputSource,
+        on_report: impl FnMut(PartReport),
+    ) -> Result<(), Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        run_bin(
+            &puzzle.to_string(),
+            &input.args(),
+            bench_time,
+            &profile_args(is_release, heap),
+            on_report,
+        )
+    }
+
+    /// Run the given puzzles on an input in the `solutions` binary, passing their reports to `on_report` in order.
+    pub fn run_registry(
+        puzzles: &[PuzzleId],
+        bench_time: Option<Duration>,
+        is_release: bool,
+        heap: HeapProfiling,
+        parallel: bool,
+        input: &InputSource,
+        on_report: impl FnMut(PartReport),
+    ) -> Result<(), Error> {
+        let mut args: Vec<String> = puzzles.iter().map(ToString::to_string).collect();
+        args.extend(input.args());
+
+        if parallel {
+            args.push("--parallel".into());
+        }
+
+        run_bin(
+            REGISTRY_BIN,
+            &args,
+            bench_time,
+            &profile_args(is_release, heap),
+            on_report,
+        )
+    }
+
+    /// Build the `solutions` binary and list the puzzles compiled into it.
+    pub fn list_registered(
+        is_release: bool,
+        heap: HeapProfiling,
+    ) -> Result<HashSet<PuzzleId>, Error> {
+        let mut args = vec!["run", "--quiet", "--bin", REGISTRY_BIN];
+        args.extend(profile_args(is_release, heap));
+
+        let output = cargo_command()
+            .args(&args)
+            .args(["--", "--list"])
+            .stderr(Stdio::inherit())
+            .output()?;
 
-        if is_release {
-            args.push("--release");
+        if !output.status.success() {
+            return Err(Error::BrokenPipe);
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.parse().ok())
+            .collect())
+    }
+
+    fn run_bin(
+        bin_name: &str,
+        bin_args: &[String],
+        bench_time: Option<Duration>,
+        profile_args: &[&str],
+        mut on_report: impl FnMut(PartReport),
+    ) -> Result<(), Error> {
+        let mut args = vec!["run", "--quiet", "--bin", bin_name];
+        args.extend(profile_args);
+
+        // results are read from the structured output of the child, regardless of the output format.
+        args.extend(["--", "--format", "json"]);
+        args.extend(bin_args.iter().map(String::as_str));
+
+        let bench_millis = bench_time.map(|t| t.as_millis().to_string());
+
+        if let Some(bench_millis) = &bench_millis {
+            // mirror `--time` flag and time budget to child invocations.
+            args.extend(["--time", "--bench-time", bench_millis]);
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while collecting the reports.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = cargo_command()
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,150 +482,228 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                eprintln!("{line}");
             });
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            let line = line?;
+            match PartReport::from_line(&line) {
+                Some(report) => on_report(report),
+                // debug output of the solution, kept apart from the reports.
+                None => eprintln!("{line}"),
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        thread.join().map_err(|_| Error::BrokenPipe)?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        if !status.success() {
+            return Err(Error::Exit(status));
+        }
+
+        Ok(())
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Collect the timings of a day from the reports of its benched phases.
+    pub fn timing_from_reports(reports: &[PartReport], day: Day) -> super::Timing {
+        let mut timing = super::Timing::for_day(day, 0_f64);
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        for report in reports.iter().filter(|r| !r.status.is_ok()) {
+            let time = match report.phase {
+                Phase::Parse => &mut timing.parse,
+                Phase::Part(1) => &mut timing.part_1,
+                Phase::Part(2) => &mut timing.part_2,
+                Phase::Part(_) => continue,
+            };
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+            *time = Some(report.status.to_string());
+        }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        for report in reports.iter().filter(|r| r.status.is_ok()) {
+            let memory = match report.phase {
+                Phase::Parse => &mut timing.parse_memory,
+                Phase::Part(1) => &mut timing.part_1_memory,
+                Phase::Part(2) => &mut timing.part_2_memory,
+                Phase::Part(_) => continue,
+            };
 
-                timings.total_nanos += nanos;
-            });
+            *memory = report.memory;
+        }
 
-        timings
-    }
+        // unsolved parts and unbenched phases are not timed.
+        for report in reports.iter().filter(|r| r.stats.is_some()) {
+            if report.phase != Phase::Parse && report.result.is_none() {
+                continue;
+            }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+            let (time, stats) = match report.phase {
+                Phase::Parse => (&mut timing.parse, &mut timing.parse_stats),
+                Phase::Part(1) => (&mut timing.part_1, &mut timing.part_1_stats),
+                Phase::Part(2) => (&mut timing.part_2, &mut timing.part_2_stats),
+                Phase::Part(_) => continue,
+            };
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+            *time = Some(format!("{:.1?}", report.time()));
+            *stats = report.stats;
+            timing.total_nanos += report.time().as_nanos() as f64;
+        }
+
+        timing
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Collect the results of both parts from the reports of a day.
+    pub fn results_from_reports(reports: &[PartReport]) -> [Option<String>; 2] {
+        let result = |part| {
+            reports
+                .iter()
+                .find(|r| r.phase == Phase::Part(part))
+                .and_then(|r| r.result.clone())
+        };
+
+        [result(1), result(2)]
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
+        use super::super::{DayOutput, Error, describe_failures};
+        use super::{results_from_reports, timing_from_reports};
         use crate::day;
+        use crate::template::PuzzleId;
+        use crate::template::memory::MemoryStats;
+        use crate::template::report::{OutputFormat, PartReport, Phase, Status};
+        use crate::template::stats::BenchStats;
+        use std::io;
+        use std::time::Duration;
+
+        fn report(phase: Phase, result: Option<&str>, millis: &[u64]) -> PartReport {
+            let samples: Vec<Duration> = millis.iter().map(|m| Duration::from_millis(*m)).collect();
+
+            PartReport {
+                puzzle: PuzzleId::new(None, day!(1)),
+                phase,
+                status: Status::Ok,
+                result: result.map(String::from),
+                error: None,
+                duration: Duration::from_millis(10),
+                stats: BenchStats::from_samples(&samples),
+                memory: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(Phase::Parse, None, &[1]),
+                    report(Phase::Part(1), Some("0"), &[2, 2, 2]),
+                    report(Phase::Part(2), Some("10"), &[3]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 6_000_000_f64);
+            assert_eq!(res.parse.unwrap(), "1.0ms");
+            assert_eq!(res.part_1.unwrap(), "2.0ms");
+            assert_eq!(res.part_1_stats.unwrap().samples, 3);
+            assert_eq!(res.part_2.unwrap(), "3.0ms");
+            assert_eq!(res.part_1_memory, None);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_heap_usage() {
+            let memory = MemoryStats {
+                peak_bytes: 2048,
+                allocations: 3,
+                total_bytes: 4096,
+            };
+            let mut profiled = report(Phase::Part(1), Some("0"), &[2]);
+            profiled.memory = Some(memory);
+
+            let res = timing_from_reports(&[profiled], day!(1));
+            assert_eq!(res.part_1_memory, Some(memory));
+            assert_eq!(res.part_2_memory, None);
+        }
+
+        #[test]
+        fn skips_missing_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(Phase::Part(1), None, &[2]),
+                    report(Phase::Part(2), Some("10"), &[]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 0_f64);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+            assert!(res.part_1_stats.is_none());
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
+        fn marks_failed_parts() {
+            let mut timed_out = report(Phase::Part(2), None, &[]);
+            timed_out.status = Status::Timeout;
+
+            let res = timing_from_reports(
+                &[report(Phase::Part(1), Some("0"), &[2]), timed_out],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.total_nanos, 2_000_000_f64);
+            assert_eq!(res.part_2.unwrap(), "TIMEOUT");
+            assert!(res.part_2_stats.is_none());
+        }
+
+        #[test]
+        fn describes_failures() {
+            let mut panicked = report(Phase::Part(2), None, &[]);
+            panicked.status = Status::Panicked;
+            let mut timed_out = report(Phase::Parse, None, &[]);
+            timed_out.status = Status::Timeout;
+
+            assert_eq!(
+                describe_failures(&[panicked, timed_out]),
+                "Day 01 Part 2 (PANICKED), Day 01 Parse (TIMEOUT)"
+            );
+        }
+
+        #[test]
+        fn reports_crashed_days() {
+            let exit = || Error::IO(io::Error::other("signal: 6 (SIGABRT)"));
+            let mut output = DayOutput::new(OutputFormat::Json);
+
+            output.start_day(day!(1));
+            output.report(report(Phase::Parse, None, &[1]));
+            output.report(report(Phase::Part(1), Some("0"), &[2]));
+            output.crash(PuzzleId::new(None, day!(1)), &exit());
+
+            // a failed phase already explains why the process ended.
+            let mut out_of_memory = report(Phase::Part(1), None, &[]);
+            out_of_memory.puzzle = PuzzleId::new(None, day!(2));
+            out_of_memory.status = Status::OutOfMemory;
+            output.start_day(day!(2));
+            output.report(out_of_memory);
+            output.crash(PuzzleId::new(None, day!(2)), &exit());
+
+            output.start_day(day!(3));
+            output.crash(PuzzleId::new(None, day!(3)), &exit());
+
+            let (_, failures) = output.finish();
+            assert_eq!(
+                describe_failures(&failures),
+                "Day 01 Part 2 (CRASHED), Day 02 Part 1 (OUT OF MEMORY), Day 03 Part 1 (CRASHED)"
+            );
+            assert_eq!(failures[0].error.as_deref(), Some("signal: 6 (SIGABRT)"));
+        }
+
+        #[test]
+        fn collects_results() {
+            let res = results_from_reports(&[
+                report(Phase::Parse, None, &[]),
+                report(Phase::Part(2), Some("#..\n.#."), &[]),
+            ]);
+            assert_eq!(res, [None, Some("#..\n.#.".to_string())]);
         }
     }
 }
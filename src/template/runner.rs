@@ -1,69 +1,516 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{Write, stdout};
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Once};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, panic, process, thread};
+
+use tinyjson::JsonValue;
 
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{self, AocClientError, SubmissionOutcome};
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+use crate::template::counting_alloc;
+use crate::template::input_source::InputSource;
+use crate::template::limits::{Limits, resident_memory};
+use crate::template::memory::MemoryStats;
+use crate::template::report::{OutputFormat, PartReport, Phase, Status};
+use crate::template::stats::BenchStats;
+use crate::template::{ANSI_ITALIC, ANSI_RESET, PuzzleId};
+
+/// The return type of a solution part: an [`Option`] if a part can only tell that it has no answer, or a
+/// [`Result`] to report why, e.g. because the input could not be parsed.
+pub trait PartResult {
+    type Answer: Display;
+
+    /// The answer of the part, or the cause of there being none.
+    fn answer(&self) -> Result<&Self::Answer, Option<String>>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    fn answer(&self) -> Result<&T, Option<String>> {
+        self.as_ref().ok_or(None)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<&T, Option<String>> {
+        self.as_ref().map_err(|e| Some(e.to_string()))
+    }
+}
+
+/// Reads the input of a solution binary from the source selected with `--input` or `--example`, see
+/// [`InputSource`]. Exits the process if the input cannot be read.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let source = InputSource::from_args().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let input = source.read(puzzle).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    if !source.is_puzzle_input() && OutputFormat::from_args() == OutputFormat::Text {
+        println!(
+            "{ANSI_ITALIC}Input: {}{ANSI_RESET}",
+            source.describe(puzzle)
+        );
+    }
+
+    input
+}
+
+/// Runs and times one part of a solution, returning the submission verdict if it was submitted.
+pub fn run_part<I, R>(
+    func: impl Fn(I) -> R + Send + Sync + 'static,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Option<SubmissionOutcome>
+where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    let format = OutputFormat::from_args();
+    let bench_time = bench_time_from_args();
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, report) = part_report(func, input, puzzle, part, bench_time, |result| {
+        if format == OutputFormat::Text {
+            print_result(result.answer().ok(), None, &part_str, "");
+            print_benching(bench_time);
+        }
+    });
+
+    print_report(&report, format);
+    exit_if_out_of_memory(&report);
+
+    submit_result(result?.answer().ok()?, puzzle, part)?.ok()
+}
+
+/// Runs and times the parsing phase of a two-phase solution, returning the parsed input.
+/// Exits the process if parsing fails, as there is nothing the parts could run on.
+pub fn run_parse<P: Send + Sync + 'static>(
+    func: impl Fn(&'static str) -> P + Send + Sync + 'static,
+    input: &'static str,
+    puzzle: PuzzleId,
+) -> &'static P {
+    let format = OutputFormat::from_args();
+    let bench_time = bench_time_from_args();
+
+    let (parsed, report) = parse_report(func, input, puzzle, bench_time, |_| {
+        if format == OutputFormat::Text {
+            print!("Parse:");
+            print_benching(bench_time);
+        }
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_report(&report, format);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match parsed {
+        Some(parsed) => parsed,
+        None => process::exit(1),
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+/// Runs and times one part of a solution without printing anything, benching it if a time budget is given.
+pub fn time_part<I, R>(
+    func: impl Fn(I) -> R + Send + Sync + 'static,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+    puzzle: PuzzleId,
+    part: u8,
+    bench_time: Option<Duration>,
+) -> PartReport
+where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    part_report(func, input, puzzle, part, bench_time, |_| {}).1
+}
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+/// Runs and times the parsing phase of a two-phase solution without printing anything.
+/// The parsed input is [`None`] if parsing exceeded its limits.
+pub fn time_parse<P: Send + Sync + 'static>(
+    func: impl Fn(&'static str) -> P + Send + Sync + 'static,
+    input: &'static str,
+    puzzle: PuzzleId,
+    bench_time: Option<Duration>,
+) -> (Option<&'static P>, PartReport) {
+    parse_report(func, input, puzzle, bench_time, |_| {})
+}
 
-        func(input)
+/// Runs a part, returning its result unless it failed.
+fn part_report<I, R>(
+    func: impl Fn(I) -> R + Send + Sync + 'static,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    bench_time: Option<Duration>,
+    hook: impl Fn(&R),
+) -> (Option<R>, PartReport)
+where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    let limits = Limits::for_puzzle(puzzle);
+
+    let (result, report) = match run_timed(func, input, bench_time, limits, hook) {
+        Ok((result, duration, stats, memory)) => {
+            let answer = result.answer();
+            let report = PartReport {
+                puzzle,
+                phase: Phase::Part(part),
+                status: Status::Ok,
+                result: answer.as_ref().ok().map(ToString::to_string),
+                error: answer.err().flatten(),
+                duration,
+                stats,
+                memory,
+            };
+            (Some(result), report)
+        }
+        Err(failure) => (None, failed_report(puzzle, Phase::Part(part), failure)),
     };
-    let base_time = timer.elapsed();
+
+    (result, report)
+}
+
+fn parse_report<P: Send + Sync + 'static>(
+    func: impl Fn(&'static str) -> P + Send + Sync + 'static,
+    input: &'static str,
+    puzzle: PuzzleId,
+    bench_time: Option<Duration>,
+    hook: impl Fn(&P),
+) -> (Option<&'static P>, PartReport) {
+    let limits = Limits::for_puzzle(puzzle);
+
+    match run_timed(func, input, bench_time, limits, hook) {
+        Ok((parsed, duration, stats, memory)) => {
+            let report = PartReport {
+                puzzle,
+                phase: Phase::Parse,
+                status: Status::Ok,
+                result: None,
+                error: None,
+                duration,
+                stats,
+                memory,
+            };
+            // the parts borrow the parsed input on threads of their own, see `run_limited`.
+            (Some(Box::leak(Box::new(parsed))), report)
+        }
+        Err(failure) => (None, failed_report(puzzle, Phase::Parse, failure)),
+    }
+}
+
+fn failed_report(puzzle: PuzzleId, phase: Phase, failure: Failure) -> PartReport {
+    PartReport {
+        puzzle,
+        phase,
+        status: failure.status,
+        result: None,
+        error: failure.error,
+        duration: failure.duration,
+        stats: None,
+        memory: None,
+    }
+}
+
+/// A part over its memory limit keeps running on its abandoned thread, so the process has to end.
+pub(crate) fn exit_if_out_of_memory(report: &PartReport) {
+    if report.status == Status::OutOfMemory {
+        process::exit(1);
+    }
+}
+
+/// Print the report of a phase in the given format.
+pub(crate) fn print_report(report: &PartReport, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => {
+            let label = report.phase.to_string();
+            let duration_str = format_duration(&report.duration, report.stats.as_ref());
+
+            if !report.status.is_ok() {
+                print!("\r");
+                print!("{label}: {ANSI_BOLD}{}{ANSI_RESET}", report.status);
+                // a crashed phase was not timed, its process ended before it could report.
+                if report.status != Status::Crashed {
+                    print!(" (after {:.1?})", report.duration);
+                }
+                match &report.error {
+                    Some(error) => println!(": {error}"),
+                    None => println!(),
+                }
+            } else if report.phase == Phase::Parse {
+                print!("\r");
+                println!("{label}:{duration_str}");
+            } else {
+                print_result(
+                    report.result.as_ref(),
+                    report.error.as_deref(),
+                    &label,
+                    &duration_str,
+                );
+            }
+
+            if let Some(stats) = &report.stats {
+                print_stats(stats, &label);
+            }
+
+            if let Some(memory) = &report.memory {
+                println!("{label} memory: {ANSI_ITALIC}{memory}{ANSI_RESET}");
+            }
+        }
+        OutputFormat::Json => println!("{}", JsonValue::from(report).stringify().unwrap()),
+        OutputFormat::Csv => println!("{}", report.to_csv_row()),
+    }
+}
+
+/// Minimum number of measured samples, regardless of the time budget.
+const MIN_SAMPLES: usize = 10;
+/// Maximum number of measured samples, to bound memory for very fast solutions.
+const MAX_SAMPLES: usize = 1_000_000;
+/// How often a run with limits is checked.
+const WATCH_INTERVAL: Duration = Duration::from_millis(10);
+/// Runs happen on threads of their own, with the stack size of a main thread.
+pub(crate) const STACK_SIZE: usize = 8 * 1024 * 1024;
+/// Default time budget for measuring samples, can be overridden with `--bench-time <ms>`.
+pub const DEFAULT_BENCH_TIME: Duration = Duration::from_secs(1);
+
+/// Run a solution part. The behavior differs depending on whether a time budget is given:
+///  1. without, the function is executed once.
+///  2. with, the function is benched (warm-up, then samples for the time budget or at least 10 samples, whatever takes longer.)
+///
+/// The first run is watched for the [`Limits`] of the puzzle. If it panics or exceeds them, the part is not
+/// benched and the failure is returned. Its heap usage is returned if the solutions are built with heap
+/// profiling, see [`heap_usage`].
+fn run_timed<I, T>(
+    func: impl Fn(I) -> T + Send + Sync + 'static,
+    input: I,
+    bench_time: Option<Duration>,
+    limits: Limits,
+    hook: impl Fn(&T),
+) -> Result<(T, Duration, Option<BenchStats>, Option<MemoryStats>), Failure>
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+{
+    let func = Arc::new(func);
+    let (result, base_time, memory) = run_limited(Arc::clone(&func), input.clone(), limits)?;
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let stats = bench_time.and_then(|budget| bench(&*func, input, budget));
+
+    Ok((result, base_time, stats, memory))
+}
+
+/// A run that did not complete.
+struct Failure {
+    status: Status,
+    /// Time until the run panicked or was abandoned.
+    duration: Duration,
+    /// The panic message and location of a panicked run.
+    error: Option<String>,
+}
+
+impl Failure {
+    fn new(status: Status, duration: Duration) -> Self {
+        Failure {
+            status,
+            duration,
+            error: None,
+        }
+    }
+}
+
+/// Runs a function once on a thread of its own, and watches it from the calling thread.
+/// A panic of the function is caught and returned as a failure.
+/// A run that exceeds its limits is abandoned: its thread cannot be stopped and keeps running until the
+/// process exits.
+fn run_limited<I, T>(
+    func: Arc<impl Fn(I) -> T + Send + Sync + 'static>,
+    input: I,
+    limits: Limits,
+) -> Result<(T, Duration, Option<MemoryStats>), Failure>
+where
+    I: Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    capture_panics_of_runs();
+
+    // the process already holds the input and the results of earlier phases, which the part is not charged for.
+    let resident_before = limits.memory.and_then(|_| resident_memory()).unwrap_or(0);
+
+    let handle = thread::Builder::new()
+        .name(RUN_THREAD_NAME.into())
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            // the usage is summarized by the runner, so the profiler does not need to write a profile.
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = dhat::Profiler::builder().testing().build();
+            #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+            counting_alloc::reset();
+
+            let timer = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+            let elapsed = timer.elapsed();
+            let _ = sender.send((result.map_err(panic_error), elapsed, heap_usage()));
+        })
+        .unwrap();
+
+    type Completed<T> = (Result<T, String>, Duration, Option<MemoryStats>);
+
+    let completed = |(result, duration, memory): Completed<T>| match result {
+        Ok(result) => Ok((result, duration, memory)),
+        Err(error) => Err(Failure {
+            error: Some(error),
+            ..Failure::new(Status::Panicked, duration)
+        }),
     };
 
-    (result, run.0, run.1)
+    if limits.is_unlimited() {
+        return match receiver.recv() {
+            Ok(result) => completed(result),
+            Err(_) => resume_panic(handle),
+        };
+    }
+
+    let watch = Instant::now();
+
+    loop {
+        match receiver.recv_timeout(WATCH_INTERVAL) {
+            Ok(result) => return completed(result),
+            Err(RecvTimeoutError::Disconnected) => resume_panic(handle),
+            Err(RecvTimeoutError::Timeout) => {
+                if limits
+                    .timeout
+                    .is_some_and(|timeout| watch.elapsed() >= timeout)
+                {
+                    return Err(Failure::new(Status::Timeout, watch.elapsed()));
+                }
+
+                if limits.memory.is_some_and(|memory| {
+                    resident_memory().is_some_and(|m| m.saturating_sub(resident_before) > memory)
+                }) {
+                    return Err(Failure::new(Status::OutOfMemory, watch.elapsed()));
+                }
+            }
+        }
+    }
+}
+
+/// Heap usage of the current run, if the solutions are built with the `count-allocs` or `dhat-heap` feature.
+fn heap_usage() -> Option<MemoryStats> {
+    #[cfg(feature = "dhat-heap")]
+    return Some(MemoryStats::from_profiler());
+
+    #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+    return Some(counting_alloc::stats());
+
+    #[cfg(not(any(feature = "count-allocs", feature = "dhat-heap")))]
+    None
+}
+
+/// The thread of a run only drops its sender without a result if it panicked outside of the function.
+fn resume_panic(handle: thread::JoinHandle<()>) -> ! {
+    match handle.join() {
+        Err(payload) => panic::resume_unwind(payload),
+        Ok(()) => unreachable!("a run always sends its result"),
+    }
+}
+
+/// Name of the threads that runs happen on, whose panics are reported instead of printed.
+const RUN_THREAD_NAME: &str = "run";
+
+thread_local! {
+    /// Location of the last panic on the current thread, recorded by the panic hook.
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook that records the location of panics on run threads, so that it can be
+/// reported with the message. Panics on other threads are printed as usual.
+fn capture_panics_of_runs() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() == Some(RUN_THREAD_NAME) {
+                let location = info.location().map(ToString::to_string);
+                PANIC_LOCATION.with(|l| *l.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Describes a caught panic of the current thread, e.g. `attempt to subtract with overflow (src/bin/01.rs:25:5)`.
+fn panic_error(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".into());
+
+    match PANIC_LOCATION.with(|l| l.borrow_mut().take()) {
+        Some(location) => format!("{message} ({location})"),
+        None => message,
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Parse the time budget of a solution binary: [`None`] without `--time`, otherwise the one passed as
+/// `--bench-time <ms>`.
+fn bench_time_from_args() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+
+    if !args.iter().any(|x| x == "--time") {
+        return None;
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let bench_time = args
+        .iter()
+        .position(|x| x == "--bench-time")
+        .and_then(|i| args.get(i + 1)?.parse().ok())
+        .map_or(DEFAULT_BENCH_TIME, Duration::from_millis);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    Some(bench_time)
+}
+
+fn print_benching(bench_time: Option<Duration>) {
+    if bench_time.is_some() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, budget: Duration) -> Option<BenchStats> {
+    // warm up caches and allocator for a tenth of the budget before measuring.
+    let warmup = Instant::now();
+    while warmup.elapsed() < budget / 10 {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    let start = Instant::now();
 
-    for _ in 0..bench_iterations {
+    while timers.len() < MIN_SAMPLES || (start.elapsed() < budget && timers.len() < MAX_SAMPLES) {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -71,30 +518,39 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, stats: Option<&BenchStats>) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(stats) => format!(" ({:.1?} @ {} samples)", stats.median, stats.samples),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
+/// Print the spread of the bench samples, e.g.
+/// `Part 1 stats: median 1.2ms, mean 1.3ms ± 0.1ms, min 1.1ms, max 2.0ms, p95 1.6ms, 523 samples, 3 outliers`
+fn print_stats(stats: &BenchStats, part: &str) {
+    println!(
+        "{part} stats: {ANSI_ITALIC}median {:.1?}, mean {:.1?} ± {:.1?}, min {:.1?}, max {:.1?}, p95 {:.1?}, {} samples, {} outliers{ANSI_RESET}",
+        stats.median,
+        stats.mean,
+        stats.std_dev,
+        stats.min,
+        stats.max,
+        stats.p95,
+        stats.samples,
+        stats.outliers
+    );
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Print the answer of a part, or the cause of there being none if it is known.
+fn print_result<T: Display>(
+    result: Option<&T>,
+    error: Option<&str>,
+    part: &str,
+    duration_str: &str,
+) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -123,7 +579,12 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                match error {
+                    Some(error) => {
+                        println!("{part}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}            ")
+                    }
+                    None => println!("{part}: ✖             "),
+                }
             }
         }
     }
@@ -131,12 +592,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
+///  2. a session token is configured.
+///  3. the answer ledger of the year does not already rule the answer out.
+///
+/// Returns the classified verdict of the server, if a submission was made.
+pub fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +623,52 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if InputSource::from_args().is_ok_and(|source| !source.is_puzzle_input()) {
+        eprintln!("Only answers for the puzzle input can be submitted.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    if let Err(e) = aoc_client::check() {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    let answer = result.to_string();
+    let mut answers = Answers::read_from_file(puzzle.year);
+
+    if let Err(refusal) = answers.check(puzzle.day, part, &answer) {
+        println!("Not submitting `{answer}`: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let outcome = aoc_client::submit(puzzle, part, &answer);
+
+    match &outcome {
+        Ok(outcome) => {
+            print_outcome(outcome, part);
+            answers.record(puzzle.day, part, &answer, *outcome);
+            if let Err(e) = answers.store_file(puzzle.year) {
+                eprintln!("failed to store answer: {e}");
+            }
+        }
+        Err(e) => eprintln!("failed to submit result: {e}"),
+    }
+
+    Some(outcome)
+}
+
+fn print_outcome(outcome: &SubmissionOutcome, part: u8) {
+    let icon = if outcome.is_correct() { "⭐" } else { "✖" };
+    println!("{icon} Part {part} answer is {ANSI_BOLD}{outcome}{ANSI_RESET}.");
+
+    match outcome {
+        SubmissionOutcome::RateLimited { wait: Some(wait) } => {
+            println!("Try again in {ANSI_ITALIC}{wait:.0?}{ANSI_RESET}.");
+        }
+        SubmissionOutcome::RateLimited { wait: None } => {
+            println!("Wait before trying again, the server did not tell for how long.");
+        }
+        _ => {}
+    }
 }
@@ -0,0 +1,166 @@
+/// Summary statistics over the samples of a benchmark run.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+/// Samples outside of `[q1 - k * iqr, q3 + k * iqr]` are rejected as outliers (Tukey's fences).
+const OUTLIER_FENCE: f64 = 1.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub outliers: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+}
+
+impl BenchStats {
+    /// Computes statistics over a set of samples, after rejecting outliers.
+    /// Returns [`None`] if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 0.25)?;
+        let q3 = percentile(&sorted, 0.75)?;
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - OUTLIER_FENCE * iqr, q3 + OUTLIER_FENCE * iqr);
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| (low..=high).contains(x))
+            .collect();
+
+        let count = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / count;
+        let variance = kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+
+        Some(Self {
+            samples: kept.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+            mean: from_nanos(mean),
+            median: from_nanos(percentile(&kept, 0.5)?),
+            min: from_nanos(*kept.first()?),
+            max: from_nanos(*kept.last()?),
+            std_dev: from_nanos(variance.sqrt()),
+            p95: from_nanos(percentile(&kept, 0.95)?),
+        })
+    }
+}
+
+/// Linear interpolation between the closest ranks of a sorted slice.
+fn percentile(sorted: &[f64], q: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = q * last as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    let weight = rank - rank.floor();
+    Some(sorted[lower] * (1.0 - weight) + sorted[upper] * weight)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let nanos = |d: Duration| JsonValue::Number(d.as_nanos() as f64);
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        map.insert("mean_nanos".into(), nanos(value.mean));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("max_nanos".into(), nanos(value.max));
+        map.insert("std_dev_nanos".into(), nanos(value.std_dev));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+            mean: from_nanos(number("mean_nanos")?),
+            median: from_nanos(number("median_nanos")?),
+            min: from_nanos(number("min_nanos")?),
+            max: from_nanos(number("max_nanos")?),
+            std_dev: from_nanos(number("std_dev_nanos")?),
+            p95: from_nanos(number("p95_nanos")?),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchStats;
+    use std::time::Duration;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = BenchStats::from_samples(&millis(&[4, 2, 3, 1, 5])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1_414_214));
+        assert_eq!(stats.p95, Duration::from_micros(4_800));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&millis(&[10, 11, 10, 12, 11, 10, 250])).unwrap();
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_millis(12));
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = BenchStats::from_samples(&millis(&[1, 2, 3])).unwrap();
+        let json = tinyjson::JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+}
@@ -1,19 +1,54 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::memory::MemoryStats;
+use crate::template::report::Status;
+use crate::template::stats::BenchStats;
+use crate::template::{Day, Year, data_dir};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// Heap usage of each phase, only recorded when timed with `--count-allocs` or `--dhat`.
+    pub parse_memory: Option<MemoryStats>,
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// A timing of a day without any timed phase.
+    pub fn for_day(day: Day, total_nanos: f64) -> Self {
+        Self {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_memory: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            total_nanos,
+        }
+    }
+
+    /// Whether the heap usage of any phase was recorded.
+    pub fn has_memory(&self) -> bool {
+        self.parse_memory.is_some() || self.part_1_memory.is_some() || self.part_2_memory.is_some()
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,16 +57,16 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of a year.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(data_dir(year).join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings from the JSON file of a year. If not present, returns empty timings.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let s = fs::read_to_string(data_dir(year).join(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -45,11 +80,22 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Days that were timed without heap profiling keep the heap usage they were last profiled with.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            if let Some(old) = self.data.iter().find(|t| t.day == timing.day)
+                && !timing.has_memory()
+            {
+                timing.parse_memory = old.parse_memory;
+                timing.part_1_memory = old.part_1_memory;
+                timing.part_2_memory = old.part_2_memory;
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,7 +104,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -67,10 +113,16 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Whether both parts of a day were timed. Parts that failed, e.g. with a `TIMEOUT`, are not.
     pub fn is_day_complete(&self, day: Day) -> bool {
+        let is_timed = |part: &Option<String>| {
+            part.as_deref()
+                .is_some_and(|part| Status::from_label(part).is_none())
+        };
+
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && is_timed(&t.part_1) && is_timed(&t.part_2))
     }
 }
 
@@ -121,9 +173,12 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        let parse = value.parse.clone().map(JsonValue::String);
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
+        map.insert("parse".into(), parse.unwrap_or(JsonValue::Null));
+
         map.insert(
             "part_1".into(),
             match part_1 {
@@ -140,6 +195,28 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        for (key, memory) in [
+            ("parse_memory", &value.parse_memory),
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            map.insert(
+                key.into(),
+                memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -168,15 +245,38 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // parse timings are optional, as only two-phase solutions report them.
+        let parse = json
+            .get("parse")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // stats are optional to stay compatible with timings stored before they were recorded.
+        let stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => BenchStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        let memory = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => MemoryStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats: stats("parse_stats")?,
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
+            parse_memory: memory("parse_memory")?,
+            part_1_memory: memory("part_1_memory")?,
+            part_2_memory: memory("part_2_memory")?,
             total_nanos,
         })
     }
@@ -194,22 +294,18 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    ..Timing::for_day(day!(1), 3e+10)
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    ..Timing::for_day(day!(2), 7e+10)
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
+                    ..Timing::for_day(day!(4), 4e+10)
                 },
             ],
         }
@@ -230,6 +326,27 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "outliers": 1, "mean_nanos": 1100000, "median_nanos": 1000000, "min_nanos": 900000, "max_nanos": 1500000, "std_dev_nanos": 50000, "p95_nanos": 1400000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median, std::time::Duration::from_millis(1));
+            assert_eq!(timing.part_2_stats, None);
+            assert_eq!(timing.part_1_memory, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_memory": { "peak_bytes": 2048, "allocations": 3, "total_bytes": 4096 }, "part_2_memory": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_memory.unwrap().peak_bytes, 2048);
+            assert_eq!(timing.part_2_memory, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -285,48 +402,54 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    ..Timing::for_day(day!(1), 3_000_000_000_f64)
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    ..Timing::for_day(day!(1), 1_000_000_000_f64)
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
-        fn handles_uncompleted_days() {
+        fn handles_timed_out_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    part_1: Some("1ms".into()),
+                    part_2: Some("TIMEOUT".into()),
+                    ..Timing::for_day(day!(1), 1_000_000_000_f64)
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_uncompleted_days() {
+            let timings = Timings {
+                data: vec![Timing::for_day(day!(1), 0.0)],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::memory::MemoryStats,
             template::timings::{Timing, Timings},
         };
 
@@ -336,12 +459,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::for_day(day!(3), 0_f64)],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -356,12 +474,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::for_day(day!(2), 0_f64)],
             };
             let merged = timings.merge(&other);
 
@@ -372,6 +485,32 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_profiled_memory() {
+            let mut timings = get_mock_timings();
+            let memory = MemoryStats {
+                peak_bytes: 2048,
+                allocations: 3,
+                total_bytes: 4096,
+            };
+            timings.data[1].part_1_memory = Some(memory);
+
+            let mut other = get_mock_timings();
+            other.data.truncate(2);
+            other.data[0].part_2_memory = Some(memory);
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data[0].part_2_memory, Some(memory));
+            assert_eq!(merged.data[1].part_1_memory, Some(memory));
+
+            let mut profiled = get_mock_timings();
+            profiled.data[1].part_2_memory = Some(memory);
+            let merged = timings.merge(&profiled);
+
+            assert_eq!(merged.data[1].part_1_memory, None);
+            assert_eq!(merged.data[1].part_2_memory, Some(memory));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
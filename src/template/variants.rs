@@ -0,0 +1,278 @@
+/// Compares alternative implementations of a part head to head, e.g. a generic version against one tuned for
+/// the input. The variants of a day are declared in its tests with [`compare_variants`](crate::compare_variants):
+///
+/// ```ignore
+/// advent_of_code::compare_variants! {
+///     1 => [
+///         "part_one" => part_one,
+///         "single_pass" => |input| _part_one_single_pass(input, Point2::new(101, 103)),
+///     ],
+/// }
+/// ```
+///
+/// `cargo compare <day>` benches the variants of every part on the puzzle input, checks that they agree on the
+/// answer and prints them ranked by their median time. Variants receive the raw input, so two-phase solutions
+/// parse it in every variant, e.g. `|input| part_one(&parse(input))`.
+use std::env;
+use std::io::{Write, stdout};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::template::report::{PartReport, Status};
+use crate::template::runner::{DEFAULT_BENCH_TIME, PartResult, time_part};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, InputSource, PuzzleId};
+
+/// Variable that `cargo compare --bench-time <ms>` passes the time budget of each variant in.
+pub const BENCH_TIME_VARIABLE: &str = "AOC_BENCH_TIME";
+
+/// A named implementation of a part.
+pub struct Variant<R> {
+    pub name: &'static str,
+    run: Arc<dyn Fn(&'static str) -> R + Send + Sync>,
+}
+
+/// Creates a variant, see [`compare_variants`](crate::compare_variants).
+pub fn variant<R>(
+    name: &'static str,
+    run: impl Fn(&'static str) -> R + Send + Sync + 'static,
+) -> Variant<R> {
+    Variant {
+        name,
+        run: Arc::new(run),
+    }
+}
+
+/// Benches the variants of a part on the puzzle input and prints them ranked by their median time.
+/// Returns the reason if the variants disagree on the answer, or if one of them did not complete.
+pub fn compare_variants<R: PartResult + Send + 'static>(
+    puzzle: impl Into<PuzzleId>,
+    part: u8,
+    variants: Vec<Variant<R>>,
+) -> Result<(), String> {
+    let puzzle = puzzle.into();
+    // parts run on threads of their own, which need the input for the lifetime of the process.
+    let input: &'static str = InputSource::Puzzle.read(puzzle)?.leak();
+    let bench_time = bench_time_from_env();
+
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+    println!("------");
+
+    let mut reports: Vec<(&'static str, PartReport)> = vec![];
+
+    for variant in variants {
+        print!("{} > {ANSI_ITALIC}benching{ANSI_RESET}", variant.name);
+        let _ = stdout().flush();
+
+        let run = variant.run;
+        let report = time_part(
+            move |input| run(input),
+            input,
+            puzzle,
+            part,
+            Some(bench_time),
+        );
+        print!("\r\x1b[2K");
+
+        // a variant over its memory limit keeps running on its abandoned thread and skews the others.
+        let is_out_of_memory = report.status == Status::OutOfMemory;
+        reports.push((variant.name, report));
+        if is_out_of_memory {
+            break;
+        }
+    }
+
+    let ranked = rank(reports);
+    for line in format_table(&ranked) {
+        println!("{line}");
+    }
+
+    let outcome = check_agreement(&ranked);
+    if let Ok(answer) = &outcome {
+        println!("Answer: {ANSI_BOLD}{answer}{ANSI_RESET}");
+    }
+    println!();
+
+    outcome
+        .map(|_| ())
+        .map_err(|problems| format!("variants of part {part} {problems}"))
+}
+
+/// Parse the time budget of each variant from [`BENCH_TIME_VARIABLE`], [`DEFAULT_BENCH_TIME`] if not set.
+fn bench_time_from_env() -> Duration {
+    env::var(BENCH_TIME_VARIABLE)
+        .ok()
+        .and_then(|millis| millis.parse().ok())
+        .map_or(DEFAULT_BENCH_TIME, Duration::from_millis)
+}
+
+/// Orders completed variants from fastest to slowest, followed by those that did not complete.
+fn rank(mut reports: Vec<(&'static str, PartReport)>) -> Vec<(&'static str, PartReport)> {
+    reports.sort_by_key(|(_, report)| (!report.status.is_ok(), report.time()));
+    reports
+}
+
+/// Formats ranked variants as an aligned table, e.g.
+/// `1  bounded   1.2ms  ±50.0µs  523  1.00x`. Variants that did not complete are listed with their status.
+fn format_table(ranked: &[(&str, PartReport)]) -> Vec<String> {
+    let fastest = ranked
+        .first()
+        .filter(|(_, report)| report.status.is_ok())
+        .map(|(_, report)| report.time());
+
+    let header = ["#", "Variant", "Median", "Std Dev", "Samples", "Relative"].map(String::from);
+
+    let rows: Vec<[String; 6]> = ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (name, report))| {
+            if !report.status.is_ok() {
+                let status = report.status.to_string();
+                return [
+                    "-".into(),
+                    (*name).into(),
+                    status,
+                    "".into(),
+                    "".into(),
+                    "".into(),
+                ];
+            }
+
+            let relative = fastest.map_or(1.0, |fastest| {
+                report.time().as_secs_f64() / fastest.as_secs_f64().max(f64::EPSILON)
+            });
+
+            [
+                (i + 1).to_string(),
+                (*name).into(),
+                format!("{:.1?}", report.time()),
+                report
+                    .stats
+                    .map_or_else(String::new, |stats| format!("±{:.1?}", stats.std_dev)),
+                report.samples().to_string(),
+                format!("{relative:.2}x"),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    [&header]
+        .into_iter()
+        .chain(&rows)
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Returns the answer that all variants agree on, or why they do not.
+fn check_agreement(ranked: &[(&str, PartReport)]) -> Result<String, String> {
+    let failed: Vec<String> = ranked
+        .iter()
+        .filter(|(_, report)| !report.status.is_ok())
+        .map(|(name, report)| format!("{name} ({})", report.status))
+        .collect();
+
+    if !failed.is_empty() {
+        return Err(format!("did not complete: {}", failed.join(", ")));
+    }
+
+    let answer = |report: &PartReport| report.result.clone().unwrap_or_else(|| "✖".into());
+
+    let Some((_, first)) = ranked.first() else {
+        return Err("are missing.".into());
+    };
+
+    if ranked
+        .iter()
+        .all(|(_, report)| answer(report) == answer(first))
+    {
+        return Ok(answer(first));
+    }
+
+    let answers: Vec<String> = ranked
+        .iter()
+        .map(|(name, report)| format!("{name}: `{}`", answer(report)))
+        .collect();
+
+    Err(format!("disagree on the answer:\n{}", answers.join("\n")))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_agreement, format_table, rank};
+    use crate::day;
+    use crate::template::stats::BenchStats;
+    use crate::template::{PartReport, Phase, Status};
+    use std::time::Duration;
+
+    fn report(result: Option<&str>, millis: &[u64]) -> PartReport {
+        let samples: Vec<Duration> = millis.iter().map(|m| Duration::from_millis(*m)).collect();
+
+        PartReport {
+            puzzle: day!(14).into(),
+            phase: Phase::Part(1),
+            status: Status::Ok,
+            result: result.map(Into::into),
+            error: None,
+            duration: Duration::from_millis(10),
+            stats: BenchStats::from_samples(&samples),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn ranks_variants_by_median() {
+        let mut panicked = report(None, &[]);
+        panicked.status = Status::Panicked;
+
+        let ranked = rank(vec![
+            ("slow", report(Some("12"), &[4, 4, 4])),
+            ("broken", panicked),
+            ("fast", report(Some("12"), &[1, 2, 3])),
+        ]);
+
+        assert_eq!(
+            format_table(&ranked),
+            [
+                "#  Variant  Median    Std Dev   Samples  Relative",
+                "1  fast     2.0ms     ±816.5µs  3        1.00x",
+                "2  slow     4.0ms     ±0.0ns    3        2.00x",
+                "-  broken   PANICKED",
+            ]
+        );
+        assert_eq!(
+            check_agreement(&ranked),
+            Err("did not complete: broken (PANICKED)".into())
+        );
+    }
+
+    #[test]
+    fn checks_agreement() {
+        let agreeing = [
+            ("a", report(Some("12"), &[1])),
+            ("b", report(Some("12"), &[2])),
+        ];
+        assert_eq!(check_agreement(&agreeing), Ok("12".into()));
+
+        let disagreeing = [("a", report(Some("12"), &[1])), ("b", report(None, &[2]))];
+        assert_eq!(
+            check_agreement(&disagreeing),
+            Err("disagree on the answer:\na: `12`\nb: `✖`".into())
+        );
+    }
+}